@@ -1,11 +1,14 @@
+use std::cell::Cell;
+
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    MouseEvent, MouseKind, Style, run,
-    widgets::{Block, Borders, Scrollable, Text},
+    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Measure,
+    Modifier, MouseEvent, MouseKind, Style, run,
+    widgets::{Block, Borders, ScrollState, Scrollable, Text},
 };
 
 struct BookReader {
-    scroll_y: u16,
+    // `draw` clamps the offset to the content, so it needs to write it back.
+    scroll: Cell<ScrollState>,
     content: String,
 }
 
@@ -50,7 +53,7 @@ But she never forgot Straw. She kept a secret diary, in which she wrote about he
         .replace("\n", " \n "); // Add spaces for wrapping simulation if we had it
 
         Self {
-            scroll_y: 0,
+            scroll: Cell::new(ScrollState::default()),
             content: content.to_string(),
         }
     }
@@ -83,17 +86,11 @@ impl Application for BookReader {
     }
 
     fn update(&mut self, action: Self::Action) -> Command {
+        let scroll = self.scroll.get_mut();
         match action {
             Action::Quit => return Command::Quit,
-            Action::ScrollUp => {
-                self.scroll_y = self.scroll_y.saturating_sub(1);
-            }
-            Action::ScrollDown => {
-                // Simple bound check (approximate lines)
-                if self.scroll_y < 50 {
-                    self.scroll_y += 1;
-                }
-            }
+            Action::ScrollUp => scroll.scroll_by(0, -1),
+            Action::ScrollDown => scroll.scroll_by(0, 1),
         }
         Command::None
     }
@@ -133,14 +130,15 @@ impl Application for BookReader {
         let body_inner = body_block.inner(body);
 
         // We render the text into a virtual area that is as wide as the screen
-        // but much taller (to hold the whole book).
+        // and as tall as the wrapped book.
         let text_widget = Text::new(&self.content).wrap(true);
 
-        let scrollable = Scrollable::new(text_widget)
-            .virtual_size(body_inner.width, 100) // Arbitrary height for now
-            .scroll(0, self.scroll_y);
+        let height = text_widget.preferred_size(body_inner.width).height;
+        let scrollable = Scrollable::new(text_widget).virtual_size(body_inner.width, height);
 
-        frame.render_widget(scrollable, body_inner);
+        let mut scroll = self.scroll.get();
+        frame.render_stateful_widget(scrollable, body_inner, &mut scroll);
+        self.scroll.set(scroll);
 
         // --- Footer ---
         frame.render_widget(
@@ -1,6 +1,6 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Style, run,
+    Application, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier, Role,
+    Style, Theme, run,
     widgets::{Block, Borders, Text},
 };
 
@@ -38,6 +38,14 @@ impl Application for Counter {
         Command::None
     }
 
+    fn theme(&self) -> Theme {
+        // Set PHOSPHOR_THEME to "light" on light terminal backgrounds.
+        std::env::var("PHOSPHOR_THEME")
+            .ok()
+            .and_then(|name| Theme::preset(&name))
+            .unwrap_or_else(Theme::dark)
+    }
+
     fn draw(&self, frame: &mut Frame) {
         let [header, body, footer] = Layout::new(
             Direction::Vertical,
@@ -54,11 +62,8 @@ impl Application for Counter {
             header,
         );
 
-        let block = Block::new()
-            .borders(Borders::ALL)
-            .title("Hello")
-            .style(Style::new().fg(Color::Magenta))
-            .title_style(Style::new().fg(Color::Green).modifier(Modifier::BOLD));
+        // Border and title colors come from the theme.
+        let block = Block::new().borders(Borders::ALL).title("Hello");
 
         let inner_area = block.inner(body);
         frame.render_widget(block, body);
@@ -66,7 +71,7 @@ impl Application for Counter {
         frame.render_widget(Text::new(format!("Count: {}", self.value)), inner_area);
 
         frame.render_widget(
-            Text::new("Press +/-, q to quit.").style(Style::new().fg(Color::Rgb(128, 128, 128))),
+            Text::new("Press +/-, q to quit.").style(frame.role_style(Role::Muted)),
             footer,
         );
     }
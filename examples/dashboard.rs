@@ -1,6 +1,5 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Style, run,
+    Application, Color, Command, Constraint, Event, Frame, Grid, KeyCode, Modifier, Style, run,
     widgets::{Block, Borders, Text},
 };
 
@@ -32,15 +31,22 @@ impl Application for State {
     }
 
     fn draw(&self, frame: &mut Frame) {
-        let [header_area, body_area, footer_area] = Layout::new(
-            Direction::Vertical,
+        // Header and footer span both columns of the grid.
+        let areas = Grid::new(
             vec![
                 Constraint::Length(3),
                 Constraint::Fill,
                 Constraint::Length(1),
             ],
+            vec![Constraint::Ratio(1, 4), Constraint::Fill],
         )
-        .split_to(frame.area());
+        .column_gutter(1)
+        .split(frame.area());
+
+        let header_area = areas.span(0, 0, 1, 2);
+        let sidebar_area = areas.cell(1, 0);
+        let content_area = areas.cell(1, 1);
+        let footer_area = areas.span(2, 0, 1, 2);
 
         // --- HEADER ---
         let header_block = Block::new()
@@ -60,16 +66,6 @@ impl Application for State {
         frame.render_widget(header_title, header_inner);
 
         // --- BODY ---
-        let [sidebar_area, _, content_area] = Layout::new(
-            Direction::Horizontal,
-            vec![
-                Constraint::Ratio(1, 4),
-                Constraint::Length(1),
-                Constraint::Fill,
-            ],
-        )
-        .split_to(body_area);
-
         // Sidebar
         let sidebar_block = Block::new()
             .borders(Borders::ALL)
@@ -1,6 +1,5 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Rect, Style, run,
+    Application, Color, Command, Event, Frame, KeyCode, Margin, Modifier, Style, run,
     widgets::{Block, Borders, Text},
 };
 
@@ -44,11 +43,11 @@ impl Application for ModalDemo {
             "Press 'm' to toggle the modal.\nPress 'q' to quit.\n\n".to_string()
                 + &"Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(10),
         );
-        frame.render_widget(text, Rect::new(2, 2, area.width - 4, area.height - 4));
+        frame.render_widget(text, area.inner(Margin::uniform(2)));
 
         // 2. Draw Modal (if active)
         if self.show_modal {
-            let modal_area = centered_rect(60, 20, area);
+            let modal_area = area.centered(percent(area.width, 60), percent(area.height, 20));
 
             // Clear the modal area (simulate a solid background)
             // We do this by drawing a block with a solid background style?
@@ -66,12 +65,7 @@ impl Application for ModalDemo {
 
             frame.render_widget(modal, modal_area);
 
-            let inner = Rect::new(
-                modal_area.x + 2,
-                modal_area.y + 2,
-                modal_area.width.saturating_sub(4),
-                modal_area.height.saturating_sub(4),
-            );
+            let inner = modal_area.inner(Margin::uniform(2));
             frame.render_widget(
                 Text::new(
                     "I am a modal!\nI am floating above the content.\n\nPress 'm' to close me.",
@@ -89,31 +83,9 @@ impl Application for ModalDemo {
     }
 }
 
-/// Helper function to center a rect using Layout
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::new(
-        Direction::Vertical,
-        vec![
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ],
-    )
-    .split(r);
-
-    let vertical_slice = popup_layout[1];
-
-    let horizontal_layout = Layout::new(
-        Direction::Horizontal,
-        vec![
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ],
-    )
-    .split(vertical_slice);
-
-    horizontal_layout[1]
+/// Returns `p` percent of `value`, without overflowing on wide terminals.
+fn percent(value: u16, p: u16) -> u16 {
+    (value as u32 * p as u32 / 100) as u16
 }
 
 fn main() -> std::io::Result<()> {
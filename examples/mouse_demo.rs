@@ -1,33 +1,58 @@
 use phosphor::{
-    Application, Color, Command, Event, Frame, KeyCode, Modifier, MouseEvent, Style, run,
+    Application, Color, Command, Event, Frame, KeyCode, Margin, Modifier, MouseEvent, MouseKind,
+    Style, run,
     widgets::{Block, Borders, Text},
 };
 
+const BUTTONS: [&str; 3] = ["red", "green", "blue"];
+
 struct MouseDemo {
     click_pos: Option<(u16, u16)>,
+    hovered: Option<String>,
     last_action: String,
 }
 
+enum Action {
+    Key(KeyCode),
+    Mouse(MouseEvent, Option<String>),
+}
+
 impl Application for MouseDemo {
-    type Action = Event;
+    type Action = Action;
 
     fn on_event(&self, event: Event) -> Option<Self::Action> {
-        Some(event)
+        match event {
+            Event::Key(key) => Some(Action::Key(key.code)),
+            _ => None,
+        }
     }
 
-    fn update(&mut self, event: Self::Action) -> Command {
-        match event {
-            Event::Key(key) => {
-                if let KeyCode::Char('q') = key.code {
-                    return Command::Quit;
-                }
-                self.last_action = format!("Key pressed: {:?}", key.code);
+    fn wants_mouse_motion(&self) -> bool {
+        // Buttons are highlighted on hover.
+        true
+    }
+
+    fn on_mouse(&self, event: MouseEvent, target: Option<&str>) -> Option<Self::Action> {
+        Some(Action::Mouse(event, target.map(str::to_string)))
+    }
+
+    fn update(&mut self, action: Self::Action) -> Command {
+        match action {
+            Action::Key(KeyCode::Char('q')) => return Command::Quit,
+            Action::Key(code) => {
+                self.last_action = format!("Key pressed: {:?}", code);
             }
-            Event::Mouse(MouseEvent { x, y, kind }) => {
+            Action::Mouse(MouseEvent { x, y, kind }, target) => {
+                if kind == MouseKind::Moved {
+                    self.hovered = target;
+                    return Command::None;
+                }
                 self.click_pos = Some((x, y));
-                self.last_action = format!("Mouse {:?} at {},{}", kind, x, y);
+                self.last_action = match target {
+                    Some(id) => format!("Mouse {:?} on '{}'", kind, id),
+                    None => format!("Mouse {:?} at {},{}", kind, x, y),
+                };
             }
-            _ => {}
         }
         Command::None
     }
@@ -43,16 +68,39 @@ impl Application for MouseDemo {
         frame.render_widget(block, area);
 
         let info = format!(
-            "Click anywhere! Press 'q' to quit.\n\nLast Action: {}",
+            "Click the buttons! Press 'q' to quit.\n\nLast Action: {}",
             self.last_action
         );
 
-        frame.render_widget(Text::new(info), Rect::new(2, 2, area.width - 4, 5));
+        let mut content = area.inner(Margin::uniform(2));
+        frame.render_widget(Text::new(info), content.take_top(5));
+
+        // Buttons: each one registers its own hit region so clicks are
+        // routed by id rather than by raw coordinates.
+        let mut row = content.take_top(3);
+        for id in BUTTONS {
+            let button_area = row.take_left(12);
+            row.take_left(2);
+
+            let hovered = self.hovered.as_deref() == Some(id);
+            let style = if hovered {
+                Style::new().fg(Color::Yellow).modifier(Modifier::BOLD)
+            } else {
+                Style::new().fg(Color::White)
+            };
+            let button = Block::new().borders(Borders::ALL).style(style);
+            let label_area = button.inner(button_area);
+            frame.render_widget(button, button_area);
+            frame.render_widget(
+                Text::new(id).style(style),
+                label_area.centered(id.len() as u16, 1),
+            );
+            frame.register_hit(button_area, id);
+        }
 
         if let Some((x, y)) = self.click_pos {
             // Draw a target at the click position
-            // We need to be careful not to draw outside the frame
-            if x < area.width && y < area.height {
+            if area.contains(x, y) {
                 frame.write_str_with_style(
                     x,
                     y,
@@ -64,11 +112,10 @@ impl Application for MouseDemo {
     }
 }
 
-use phosphor::Rect;
-
 fn main() -> std::io::Result<()> {
     run(MouseDemo {
         click_pos: None,
+        hovered: None,
         last_action: "None".to_string(),
     })
 }
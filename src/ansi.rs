@@ -0,0 +1,544 @@
+//! The `ansi` module converts ANSI-escaped output into [`StyledText`].
+//!
+//! It is the reverse of [`Style::to_ansi`]: SGR sequences (`ESC [ ... m`)
+//! update the current style, and text is collected into styled [`Span`]s.
+//! This makes it possible to display the colored output of tools like `git`
+//! or `cargo` inside a pane without writing escape codes into cells.
+//!
+//! Supported SGR parameters:
+//! * Reset (`0`), modifiers (`1-9`, `53`) and their resets (`22-29`, `55`).
+//! * Underline shapes (`4:0` to `4:5`).
+//! * 16 colors (`30-37`, `40-47`, `90-97`, `100-107`) and defaults (`39`, `49`).
+//! * 256 colors (`38;5;n`) and RGB (`38;2;r;g;b`), including the colon forms,
+//!   for the foreground, background (`48`) and underline (`58`, reset by `59`).
+//!
+//! Every other escape sequence (cursor movement, erase, OSC hyperlinks,
+//! charset selection, ...) is stripped. A carriage return not followed by a
+//! newline discards the current line, which keeps only the final state of
+//! progress bars. Tabs are expanded to the next multiple of 8 columns.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Color, ansi};
+//!
+//! let text = ansi::parse(b"\x1b[1;31merror\x1b[0m: oops\n");
+//! let spans = &text.lines[0].spans;
+//! assert_eq!(spans[0].content, "error");
+//! assert_eq!(spans[0].style.foreground, Some(Color::Red));
+//! assert_eq!(spans[1].content, ": oops");
+//! ```
+
+use crate::input::utf8_char_width;
+use crate::text::char_width;
+use crate::{Color, Line, Modifier, Span, Style, StyledText, UnderlineStyle};
+
+/// The width of a tab stop, in columns.
+const TAB_WIDTH: usize = 8;
+
+/// Escape sequences longer than this are considered malformed and dropped.
+const MAX_SEQUENCE_LEN: usize = 4096;
+
+/// Parses a complete byte stream into styled text.
+///
+/// Invalid UTF-8 is replaced with `U+FFFD`.
+pub fn parse(bytes: &[u8]) -> StyledText {
+    let mut parser = AnsiParser::new();
+    parser.push(bytes);
+    parser.finish()
+}
+
+impl StyledText {
+    /// Parses ANSI-escaped bytes into styled text. See the [`ansi`](crate::ansi) module.
+    pub fn from_ansi(bytes: &[u8]) -> Self {
+        parse(bytes)
+    }
+}
+
+/// An incremental ANSI parser.
+///
+/// Bytes can be pushed in arbitrary chunks (e.g. as they are read from a
+/// child process); escape sequences and UTF-8 characters split across
+/// chunks are buffered until complete.
+#[derive(Debug, Clone, Default)]
+pub struct AnsiParser {
+    style: Style,
+    lines: Vec<Line>,
+    text: String,
+    column: usize,
+    pending: Vec<u8>,
+}
+
+/// The result of scanning an escape sequence.
+enum Sequence {
+    /// More bytes are needed.
+    Incomplete,
+    /// A complete sequence of the given length; `Some` holds SGR parameters.
+    Complete(usize, Option<String>),
+}
+
+impl AnsiParser {
+    /// Creates a new parser with the default style.
+    pub fn new() -> Self {
+        Self {
+            lines: vec![Line::default()],
+            ..Self::default()
+        }
+    }
+
+    /// Returns the style that will be applied to the next character.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Feeds bytes into the parser.
+    pub fn push(&mut self, bytes: &[u8]) {
+        let mut data = std::mem::take(&mut self.pending);
+        data.extend_from_slice(bytes);
+
+        let mut i = 0;
+        while i < data.len() {
+            match data[i] {
+                0x1b => match scan_escape(&data[i..]) {
+                    Sequence::Incomplete => break,
+                    Sequence::Complete(len, sgr) => {
+                        if let Some(params) = sgr {
+                            self.flush();
+                            apply_sgr(&mut self.style, &params);
+                        }
+                        i += len;
+                    }
+                },
+                b'\n' => {
+                    self.new_line();
+                    i += 1;
+                }
+                b'\r' => match data.get(i + 1) {
+                    None => break,
+                    Some(b'\n') => i += 1,
+                    Some(_) => {
+                        self.clear_line();
+                        i += 1;
+                    }
+                },
+                b'\t' => {
+                    let spaces = TAB_WIDTH - self.column % TAB_WIDTH;
+                    self.push_str(&" ".repeat(spaces));
+                    i += 1;
+                }
+                b if b < 0x20 || b == 0x7f => i += 1,
+                b => {
+                    let width = utf8_char_width(b).max(1);
+                    if i + width > data.len() {
+                        break;
+                    }
+                    match std::str::from_utf8(&data[i..i + width]) {
+                        Ok(s) => {
+                            self.push_str(s);
+                            i += width;
+                        }
+                        // Only skip the invalid prefix: the next byte may
+                        // start an escape sequence or another character.
+                        Err(e) => {
+                            self.push_str("\u{FFFD}");
+                            i += e.error_len().unwrap_or(1);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.pending = data.split_off(i);
+    }
+
+    /// Flushes pending text and returns the parsed lines.
+    ///
+    /// Incomplete trailing escape sequences are dropped. A trailing newline
+    /// does not produce an empty last line.
+    pub fn finish(mut self) -> StyledText {
+        if self.pending.first() == Some(&b'\r') {
+            self.clear_line();
+        } else if !self.pending.is_empty() && self.pending[0] != 0x1b {
+            self.push_str("\u{FFFD}");
+        }
+        self.flush();
+
+        if self.lines.len() > 1 && self.lines.last().is_some_and(|l| l.spans.is_empty()) {
+            self.lines.pop();
+        }
+        StyledText::new(self.lines)
+    }
+
+    /// Helper to append text in the current style.
+    fn push_str(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.column += s.chars().map(char_width).sum::<usize>();
+    }
+
+    /// Helper to move pending text into the current line as a span.
+    fn flush(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        let line = self.lines.last_mut().expect("at least one line");
+        match line.spans.last_mut() {
+            Some(last) if last.style == self.style => last.content.push_str(&self.text),
+            _ => line.spans.push(Span::styled(self.text.clone(), self.style)),
+        }
+        self.text.clear();
+    }
+
+    /// Helper to start a new line.
+    fn new_line(&mut self) {
+        self.flush();
+        self.lines.push(Line::default());
+        self.column = 0;
+    }
+
+    /// Helper to discard the current line (carriage return).
+    fn clear_line(&mut self) {
+        self.text.clear();
+        if let Some(line) = self.lines.last_mut() {
+            line.spans.clear();
+        }
+        self.column = 0;
+    }
+}
+
+/// Helper to scan an escape sequence starting at `data[0] == ESC`.
+fn scan_escape(data: &[u8]) -> Sequence {
+    let Some(&kind) = data.get(1) else {
+        return Sequence::Incomplete;
+    };
+
+    match kind {
+        // CSI: parameters 0x30-0x3F, intermediates 0x20-0x2F, final 0x40-0x7E.
+        b'[' => {
+            for (i, &b) in data.iter().enumerate().skip(2) {
+                if (0x40..=0x7e).contains(&b) {
+                    let params = &data[2..i];
+                    let private = params.first().is_some_and(|p| (b'<'..=b'?').contains(p));
+                    let sgr = (b == b'm' && !private)
+                        .then(|| String::from_utf8_lossy(params).into_owned());
+                    return Sequence::Complete(i + 1, sgr);
+                }
+                if !(0x20..=0x3f).contains(&b) {
+                    // Malformed: drop the introducer and what we scanned.
+                    return Sequence::Complete(i, None);
+                }
+            }
+            incomplete_or_drop(data)
+        }
+        // OSC, DCS, SOS, PM, APC: strings terminated by BEL or ST (ESC \).
+        b']' | b'P' | b'X' | b'^' | b'_' => {
+            for i in 2..data.len() {
+                if data[i] == 0x07 && kind == b']' {
+                    return Sequence::Complete(i + 1, None);
+                }
+                if data[i] == 0x1b && data.get(i + 1) == Some(&b'\\') {
+                    return Sequence::Complete(i + 2, None);
+                }
+            }
+            incomplete_or_drop(data)
+        }
+        // Charset designation takes one more byte.
+        b'(' | b')' | b'*' | b'+' => {
+            if data.len() < 3 {
+                Sequence::Incomplete
+            } else {
+                Sequence::Complete(3, None)
+            }
+        }
+        _ => Sequence::Complete(2, None),
+    }
+}
+
+/// Helper to wait for more bytes, unless the sequence is unreasonably long.
+fn incomplete_or_drop(data: &[u8]) -> Sequence {
+    if data.len() > MAX_SEQUENCE_LEN {
+        Sequence::Complete(data.len(), None)
+    } else {
+        Sequence::Incomplete
+    }
+}
+
+/// Helper to apply SGR parameters to a style.
+fn apply_sgr(style: &mut Style, params: &str) {
+    if params.is_empty() {
+        *style = Style::default();
+        return;
+    }
+
+    let mut params = params.split(';').peekable();
+    while let Some(param) = params.next() {
+        // Colon sub-parameters: `38:2::r:g:b`, `38:5:n` or `4:3`.
+        if param.contains(':') {
+            let mut sub = param.split(':');
+            let code = sub.next().and_then(|c| c.parse::<u16>().ok());
+            let rest: Vec<&str> = sub.collect();
+            if code == Some(4) {
+                apply_underline(style, rest.first().copied().unwrap_or("1"));
+                continue;
+            }
+            let color = match rest.as_slice() {
+                ["5", n] => n.parse().ok().map(Color::Indexed),
+                ["2", _, r, g, b] | ["2", r, g, b] => rgb(r, g, b),
+                _ => None,
+            };
+            if let (Some(code), Some(color)) = (code, color) {
+                set_color(style, code, color);
+            }
+            continue;
+        }
+
+        let code = match param {
+            "" => 0,
+            _ => match param.parse::<u16>() {
+                Ok(code) => code,
+                Err(_) => continue,
+            },
+        };
+
+        match code {
+            0 => *style = Style::default(),
+            1 => style.modifiers.insert(Modifier::BOLD),
+            2 => style.modifiers.insert(Modifier::DIM),
+            3 => style.modifiers.insert(Modifier::ITALIC),
+            4 => apply_underline(style, "1"),
+            5 => style.modifiers.insert(Modifier::SLOW_BLINK),
+            6 => style.modifiers.insert(Modifier::RAPID_BLINK),
+            7 => style.modifiers.insert(Modifier::REVERSED),
+            8 => style.modifiers.insert(Modifier::HIDDEN),
+            9 => style.modifiers.insert(Modifier::STRIKETHROUGH),
+            22 => style.modifiers.remove(Modifier::BOLD | Modifier::DIM),
+            23 => style.modifiers.remove(Modifier::ITALIC),
+            24 => apply_underline(style, "0"),
+            25 => style
+                .modifiers
+                .remove(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+            27 => style.modifiers.remove(Modifier::REVERSED),
+            28 => style.modifiers.remove(Modifier::HIDDEN),
+            29 => style.modifiers.remove(Modifier::STRIKETHROUGH),
+            30..=37 => style.foreground = Some(basic_color(code - 30)),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(basic_color(code - 40)),
+            49 => style.background = None,
+            53 => style.modifiers.insert(Modifier::OVERLINE),
+            55 => style.modifiers.remove(Modifier::OVERLINE),
+            59 => style.underline_color = None,
+            90..=97 => style.foreground = Some(bright_color(code - 90)),
+            100..=107 => style.background = Some(bright_color(code - 100)),
+            38 | 48 | 58 => {
+                let color = match params.next() {
+                    Some("5") => params
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .map(Color::Indexed),
+                    Some("2") => {
+                        let (r, g, b) = (params.next(), params.next(), params.next());
+                        match (r, g, b) {
+                            (Some(r), Some(g), Some(b)) => rgb(r, g, b),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(color) = color {
+                    set_color(style, code, color);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Helper to set the foreground (`38`), background (`48`) or underline
+/// (`58`) color.
+fn set_color(style: &mut Style, code: u16, color: Color) {
+    match code {
+        38 => style.foreground = Some(color),
+        48 => style.background = Some(color),
+        58 => style.underline_color = Some(color),
+        _ => {}
+    }
+}
+
+/// Helper to apply an underline shape (`4:n`), where `0` removes it.
+fn apply_underline(style: &mut Style, shape: &str) {
+    let shape = match shape {
+        "0" => {
+            style.modifiers.remove(Modifier::UNDERLINE);
+            style.underline_style = None;
+            return;
+        }
+        "2" => Some(UnderlineStyle::Double),
+        "3" => Some(UnderlineStyle::Curly),
+        "4" => Some(UnderlineStyle::Dotted),
+        "5" => Some(UnderlineStyle::Dashed),
+        _ => None,
+    };
+    style.modifiers.insert(Modifier::UNDERLINE);
+    style.underline_style = shape;
+}
+
+/// Helper to parse an RGB triplet.
+fn rgb(r: &str, g: &str, b: &str) -> Option<Color> {
+    Some(Color::Rgb(
+        r.parse().ok()?,
+        g.parse().ok()?,
+        b.parse().ok()?,
+    ))
+}
+
+/// Helper to map `0-7` to the standard colors.
+fn basic_color(n: u16) -> Color {
+    [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ][n as usize]
+}
+
+/// Helper to map `0-7` to the bright colors.
+fn bright_color(n: u16) -> Color {
+    [
+        Color::BrightBlack,
+        Color::BrightRed,
+        Color::BrightGreen,
+        Color::BrightYellow,
+        Color::BrightBlue,
+        Color::BrightMagenta,
+        Color::BrightCyan,
+        Color::BrightWhite,
+    ][n as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &StyledText) -> Vec<Vec<(&str, Style)>> {
+        text.lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| (s.content.as_str(), s.style))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ansi_basic_colors_and_reset() {
+        let text = parse(b"\x1b[31mred\x1b[0m plain \x1b[1;44mbold\x1b[22mnormal");
+        assert_eq!(
+            spans(&text),
+            vec![vec![
+                ("red", Style::new().fg(Color::Red)),
+                (" plain ", Style::default()),
+                (
+                    "bold",
+                    Style::new().bg(Color::Blue).modifier(Modifier::BOLD)
+                ),
+                ("normal", Style::new().bg(Color::Blue)),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_ansi_extended_colors() {
+        let text = parse(b"\x1b[38;5;208ma\x1b[48;2;1;2;3mb\x1b[38:2::4:5:6mc\x1b[39;49;92md");
+        let line = &text.lines[0].spans;
+        assert_eq!(line[0].style.foreground, Some(Color::Indexed(208)));
+        assert_eq!(line[1].style.background, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(line[2].style.foreground, Some(Color::Rgb(4, 5, 6)));
+        assert_eq!(line[3].style, Style::new().fg(Color::BrightGreen));
+    }
+
+    #[test]
+    fn test_ansi_round_trips_to_ansi() {
+        let style = Style::new()
+            .fg(Color::Rgb(10, 20, 30))
+            .bg(Color::Indexed(17))
+            .modifier(Modifier::BOLD | Modifier::ITALIC | Modifier::UNDERLINE);
+
+        let mut bytes = style.to_ansi().into_bytes();
+        bytes.extend_from_slice(b"x");
+
+        let text = parse(&bytes);
+        assert_eq!(text.lines[0].spans[0].style, style);
+    }
+
+    #[test]
+    fn test_ansi_round_trips_extended_attributes() {
+        let style = Style::new()
+            .fg(Color::Red)
+            .modifier(Modifier::STRIKETHROUGH | Modifier::OVERLINE | Modifier::SLOW_BLINK)
+            .underline_style(UnderlineStyle::Dashed)
+            .underline_color(Color::Rgb(1, 2, 3));
+
+        let mut bytes = style.to_ansi().into_bytes();
+        bytes.extend_from_slice(b"x\x1b[24;29;55;25;59my");
+
+        let text = parse(&bytes);
+        assert_eq!(text.lines[0].spans[0].style, style);
+        assert_eq!(text.lines[0].spans[1].style, Style::new().fg(Color::Red));
+    }
+
+    #[test]
+    fn test_ansi_strips_other_sequences() {
+        let input = b"\x1b[2K\x1b[1Ga\x1b]8;;http://x\x07link\x1b]8;;\x1b\\\x1b(Bb\x1b[?25lc";
+        let text = parse(input);
+        assert_eq!(spans(&text), vec![vec![("alinkbc", Style::default())]]);
+    }
+
+    #[test]
+    fn test_ansi_lines_tabs_and_carriage_returns() {
+        let text = parse(b"one\r\nab\tc\n 50%\r100%\n");
+        assert_eq!(text.height(), 3);
+        assert_eq!(text.lines[0].spans[0].content, "one");
+        assert_eq!(text.lines[1].spans[0].content, "ab      c");
+        assert_eq!(text.lines[2].spans[0].content, "100%");
+    }
+
+    #[test]
+    fn test_ansi_fragmented_input() {
+        let mut parser = AnsiParser::new();
+        parser.push(b"\x1b[3");
+        parser.push(b"2mok \xc3");
+        parser.push(b"\xa9\x1b");
+        parser.push(b"[0m!");
+
+        let text = parser.finish();
+        assert_eq!(
+            spans(&text),
+            vec![vec![
+                ("ok é", Style::new().fg(Color::Green)),
+                ("!", Style::default())
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_ansi_invalid_utf8_is_replaced() {
+        let text = parse(b"a\xffb");
+        assert_eq!(text.lines[0].spans[0].content, "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_ansi_truncated_utf8_keeps_next_escape() {
+        let text = parse(b"\xc3\x1b[31mred\xe2\x82!");
+        assert_eq!(
+            spans(&text),
+            vec![vec![
+                ("\u{FFFD}", Style::default()),
+                ("red\u{FFFD}!", Style::new().fg(Color::Red))
+            ]]
+        );
+    }
+}
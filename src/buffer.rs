@@ -3,11 +3,28 @@
 //! A [`Buffer`] represents a single frame of the TUI. By comparing two buffers,
 //! the framework can perform "diff-rendering," only updating the parts of the
 //! terminal that have actually changed.
+//!
+//! # Damage Tracking
+//! Every `set_*` call flags the touched row as *dirty*. [`Buffer::diff_dirty`]
+//! only compares rows that are dirty in either buffer, and [`Buffer::reset`]
+//! only clears the rows that were touched, so a buffer can be reused from one
+//! frame to the next without paying for a full scan.
+//!
+//! Each row also keeps a fingerprint of its content, updated on every write.
+//! An MVU `draw` rewrites every row each frame, so every row ends up dirty;
+//! the fingerprints let `diff_dirty` skip the rows whose content is the same
+//! as in the previous frame without comparing their cells.
+//!
+//! Writing to [`Buffer::content`] directly bypasses damage tracking; call
+//! [`Buffer::mark_dirty`] (or [`Buffer::mark_all_dirty`]) afterwards.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 use crate::{Rect, Style};
 
 /// A single character on the screen with its associated style.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Cell {
     /// The character to display in this cell.
     pub symbol: char,
@@ -32,8 +49,33 @@ pub struct Change {
     pub cell: Cell,
 }
 
+/// A vertical shift of a band of rows between two frames.
+///
+/// Produced by [`Buffer::detect_scroll`] and [`Buffer::detect_scroll_in`] so
+/// that the renderer can move the rows with a terminal scroll region instead
+/// of repainting them. Regions narrower than the buffer need left and right
+/// margins (DECSLRM), which not every terminal supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scroll {
+    /// The first row of the scrolled region.
+    pub top: u16,
+    /// The row after the last row of the scrolled region (exclusive).
+    pub bottom: u16,
+    /// The first column of the scrolled region.
+    pub left: u16,
+    /// The column after the last column of the scrolled region (exclusive).
+    pub right: u16,
+    /// The number of rows the content moved.
+    ///
+    /// Positive values move content up (new rows appear at the bottom), as
+    /// when a log view follows its tail. Negative values move it down.
+    pub amount: i32,
+}
+
 /// A 2D grid of [`Cell`]s representing a terminal frame.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality only compares the size and the cells, not the dirty flags.
+#[derive(Debug)]
 pub struct Buffer {
     /// The width of the buffer in columns.
     pub width: u16,
@@ -41,18 +83,122 @@ pub struct Buffer {
     pub height: u16,
     /// The linear storage of cells (row-major order).
     pub content: Vec<Cell>,
+    /// One flag per row, set when any cell of the row is written.
+    dirty: Vec<bool>,
+    /// One fingerprint per row, combining the fingerprints of its cells.
+    fingerprints: Vec<u64>,
+}
+
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            content: self.content.clone(),
+            dirty: self.dirty.clone(),
+            fingerprints: self.fingerprints.clone(),
+        }
+    }
+
+    /// Copies `source` into `self`, reusing the existing cell storage.
+    fn clone_from(&mut self, source: &Self) {
+        self.width = source.width;
+        self.height = source.height;
+        self.content.clone_from(&source.content);
+        self.dirty.clone_from(&source.dirty);
+        self.fingerprints.clone_from(&source.fingerprints);
+    }
 }
 
+impl PartialEq for Buffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.content == other.content
+    }
+}
+
+impl Eq for Buffer {}
+
 impl Buffer {
     /// Creates a new buffer of the given size, filled with default cells.
+    ///
+    /// No row is dirty in a new buffer.
     pub fn new(width: u16, height: u16) -> Self {
         Self {
             width,
             height,
-            content: vec![Cell::default(); (width * height) as usize],
+            content: vec![Cell::default(); width as usize * height as usize],
+            dirty: vec![false; height as usize],
+            fingerprints: vec![0; height as usize],
+        }
+    }
+
+    /// Clears the buffer back to default cells so it can be reused for the next frame.
+    ///
+    /// Only dirty rows are cleared (untouched rows are already blank), and
+    /// every dirty flag is reset afterwards.
+    pub fn reset(&mut self) {
+        let width = self.width as usize;
+        for (y, dirty) in self.dirty.iter_mut().enumerate() {
+            if *dirty {
+                self.content[y * width..(y + 1) * width].fill(Cell::default());
+                self.fingerprints[y] = 0;
+                *dirty = false;
+            }
         }
     }
 
+    /// Resizes the buffer, discarding its content.
+    ///
+    /// Does nothing if the size is unchanged.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        if self.width != width || self.height != height {
+            *self = Self::new(width, height);
+        }
+    }
+
+    /// Flags the given row as dirty and recomputes its fingerprint.
+    ///
+    /// Does nothing if the row is out of bounds.
+    pub fn mark_dirty(&mut self, y: u16) {
+        if let Some(flag) = self.dirty.get_mut(y as usize) {
+            *flag = true;
+            self.fingerprints[y as usize] = self.compute_fingerprint(y);
+        }
+    }
+
+    /// Flags every row as dirty.
+    pub fn mark_all_dirty(&mut self) {
+        for y in 0..self.height {
+            self.mark_dirty(y);
+        }
+    }
+
+    /// Clears every dirty flag without touching the cells.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.fill(false);
+    }
+
+    /// Returns `true` if any cell of the given row was written since the last reset.
+    pub fn is_row_dirty(&self, y: u16) -> bool {
+        self.dirty.get(y as usize).copied().unwrap_or(false)
+    }
+
+    /// Returns an iterator over the indices of the dirty rows.
+    pub fn dirty_rows(&self) -> impl Iterator<Item = u16> + '_ {
+        self.dirty
+            .iter()
+            .enumerate()
+            .filter(|(_, dirty)| **dirty)
+            .map(|(y, _)| y as u16)
+    }
+
+    /// Returns the symbols of a row as a string, to compare rendered rows in
+    /// tests.
+    #[cfg(test)]
+    pub(crate) fn row_symbols(&self, y: u16) -> String {
+        (0..self.width).map(|x| self.get(x, y).symbol).collect()
+    }
+
     /// Returns a reference to the cell at the given coordinates.
     ///
     /// # Panics
@@ -75,46 +221,83 @@ impl Buffer {
             return;
         }
         let idx = self.index(x, y);
-        self.content[idx].symbol = symbol;
+        let cell = Cell {
+            symbol,
+            ..self.content[idx]
+        };
+        self.write_cell(x, y, cell);
     }
 
     /// Sets the style of the cell at the given coordinates.
     ///
-    /// Does nothing if the coordinates are out of bounds.
+    /// The style is [resolved](Style::resolved). Does nothing if the
+    /// coordinates are out of bounds.
     pub fn set_style(&mut self, x: u16, y: u16, style: Style) {
         if x >= self.width || y >= self.height {
             return;
         }
         let idx = self.index(x, y);
-        self.content[idx].style = style;
+        let cell = Cell {
+            style: style.resolved(),
+            ..self.content[idx]
+        };
+        self.write_cell(x, y, cell);
     }
 
     /// Sets both the character and the style at the given coordinates.
     ///
-    /// Does nothing if the coordinates are out of bounds.
+    /// The style is [resolved](Style::resolved). Does nothing if the
+    /// coordinates are out of bounds.
     pub fn set_with_style(&mut self, x: u16, y: u16, symbol: char, style: Style) {
         if x >= self.width || y >= self.height {
             return;
         }
-        let idx = self.index(x, y);
-        self.content[idx].symbol = symbol;
-        self.content[idx].style = style;
+        let style = style.resolved();
+        self.write_cell(x, y, Cell { symbol, style });
     }
 
     /// Sets the cell at the given coordinates.
     ///
-    /// Does nothing if the coordinates are out of bounds.
+    /// The style is [resolved](Style::resolved). Does nothing if the
+    /// coordinates are out of bounds.
     pub fn set_cell(&mut self, x: u16, y: u16, cell: Cell) {
         if x >= self.width || y >= self.height {
             return;
         }
+        let style = cell.style.resolved();
+        self.write_cell(x, y, Cell { style, ..cell });
+    }
+
+    /// Helper to write an in-bounds cell, updating the row's dirty flag and
+    /// fingerprint.
+    fn write_cell(&mut self, x: u16, y: u16, cell: Cell) {
         let idx = self.index(x, y);
-        self.content[idx] = cell;
+        let old = std::mem::replace(&mut self.content[idx], cell);
+        self.fingerprints[y as usize] ^= cell_fingerprint(x, &old) ^ cell_fingerprint(x, &cell);
+        self.dirty[y as usize] = true;
+    }
+
+    /// Helper to compute the fingerprint of a row from its cells.
+    fn compute_fingerprint(&self, y: u16) -> u64 {
+        let start = y as usize * self.width as usize;
+        self.content[start..start + self.width as usize]
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (x, cell)| acc ^ cell_fingerprint(x as u16, cell))
     }
 
     /// Helper to convert 2D coordinates to a 1D index.
+    ///
+    /// The math is done in `usize` so that buffers larger than `u16::MAX`
+    /// cells (e.g. 300x300) don't overflow.
     fn index(&self, x: u16, y: u16) -> usize {
-        ((y * self.width) + x) as usize
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Helper to convert a 1D index back to 2D coordinates.
+    fn position(&self, i: usize) -> (u16, u16) {
+        let width = self.width as usize;
+        ((i % width) as u16, (i / width) as u16)
     }
 
     /// Compares this buffer with another and returns the list of changed cells.
@@ -128,20 +311,56 @@ impl Buffer {
                 .content
                 .iter()
                 .enumerate()
-                .map(|(i, cell)| Change {
-                    x: (i as u16) % self.width,
-                    y: (i as u16) / self.width,
-                    cell: *cell,
+                .map(|(i, cell)| {
+                    let (x, y) = self.position(i);
+                    Change { x, y, cell: *cell }
                 })
                 .collect();
         } else {
             for (i, (new_cell, old_cell)) in
                 self.content.iter().zip(other.content.iter()).enumerate()
             {
+                if new_cell != old_cell {
+                    let (x, y) = self.position(i);
+                    changes.push(Change {
+                        x,
+                        y,
+                        cell: *new_cell,
+                    })
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Compares this buffer with the previous frame, only looking at rows that
+    /// are dirty in either buffer.
+    ///
+    /// A row that is clean in both buffers was left untouched (and therefore
+    /// blank) in both frames, so it can't have changed. A dirty row whose
+    /// fingerprint matches the previous frame's is skipped as well. If the
+    /// sizes differ, every cell is reported, as with [`diff`](Self::diff).
+    pub fn diff_dirty(&self, previous: &Buffer) -> Vec<Change> {
+        if self.width != previous.width || self.height != previous.height {
+            return self.diff(previous);
+        }
+
+        let width = self.width as usize;
+        let mut changes: Vec<Change> = Vec::new();
+
+        for y in 0..self.height {
+            if !self.row_changed(previous, y) {
+                continue;
+            }
+            let start = y as usize * width;
+            let new_row = &self.content[start..start + width];
+            let old_row = &previous.content[start..start + width];
+            for (x, (new_cell, old_cell)) in new_row.iter().zip(old_row).enumerate() {
                 if new_cell != old_cell {
                     changes.push(Change {
-                        x: (i as u16) % self.width,
-                        y: (i as u16) / self.width,
+                        x: x as u16,
+                        y,
                         cell: *new_cell,
                     })
                 }
@@ -151,19 +370,207 @@ impl Buffer {
         changes
     }
 
+    /// Returns `true` if row `y` may differ from the same row of `previous`,
+    /// judging by the dirty flags and fingerprints alone.
+    ///
+    /// Rows for which this returns `false` are skipped by
+    /// [`diff_dirty`](Self::diff_dirty). Both buffers must have the same size.
+    pub fn row_changed(&self, previous: &Buffer, y: u16) -> bool {
+        (self.is_row_dirty(y) || previous.is_row_dirty(y))
+            && self.fingerprints[y as usize] != previous.fingerprints[y as usize]
+    }
+
+    /// Returns the smallest area containing every cell that differs from
+    /// `previous`, or `None` if nothing changed or the sizes differ.
+    ///
+    /// Only rows that are dirty in either buffer are compared.
+    pub fn changed_area(&self, previous: &Buffer) -> Option<Rect> {
+        if self.width != previous.width || self.height != previous.height {
+            return None;
+        }
+
+        let width = self.width as usize;
+        let mut area: Option<Rect> = None;
+        for y in 0..self.height {
+            if !self.row_changed(previous, y) {
+                continue;
+            }
+            let start = y as usize * width;
+            let new_row = &self.content[start..start + width];
+            let old_row = &previous.content[start..start + width];
+            let changed = |x: &usize| new_row[*x] != old_row[*x];
+            let Some(left) = (0..width).find(changed) else {
+                continue;
+            };
+            let right = (0..width).rfind(changed).unwrap_or(left) + 1;
+            let row = Rect::new(left as u16, y, (right - left) as u16, 1);
+            area = Some(area.map_or(row, |area| area.union(row)));
+        }
+        area
+    }
+
+    /// Looks for a band of full-width rows that moved vertically between
+    /// `previous` and this buffer.
+    ///
+    /// See [`detect_scroll_in`](Self::detect_scroll_in).
+    pub fn detect_scroll(&self, previous: &Buffer) -> Option<Scroll> {
+        self.detect_scroll_in(previous, Rect::new(0, 0, self.width, self.height))
+    }
+
+    /// Looks for a band of rows that moved vertically within `area` between
+    /// `previous` and this buffer, ignoring the columns outside `area`.
+    ///
+    /// This finds a log pane scrolling next to a static sidebar, when `area`
+    /// covers the pane (e.g. from [`changed_area`](Self::changed_area)).
+    ///
+    /// Only rows that are dirty in either buffer are considered. The shift is
+    /// chosen to maximise the number of changed rows it explains, and is only
+    /// returned if it explains at least half of the changed band. Rows are
+    /// compared by hash, so a collision can only make the shift less useful,
+    /// never incorrect: the caller still diffs the band afterwards.
+    pub fn detect_scroll_in(&self, previous: &Buffer, area: Rect) -> Option<Scroll> {
+        let area = area.intersection(Rect::new(0, 0, self.width, self.height));
+        if self.width != previous.width || self.height != previous.height || area.is_empty() {
+            return None;
+        }
+        let columns = area.x as usize..area.right() as usize;
+
+        let rows: Vec<u16> = (area.y..area.bottom())
+            .filter(|&y| self.row_changed(previous, y))
+            .collect();
+        let (&first, &last) = (rows.first()?, rows.last()?);
+
+        let new_hashes: Vec<u64> = (first..=last)
+            .map(|y| self.row_hash(y, columns.clone()))
+            .collect();
+        let old_hashes: Vec<u64> = (first..=last)
+            .map(|y| previous.row_hash(y, columns.clone()))
+            .collect();
+
+        // Narrow the band down to the rows that actually changed.
+        let changed = |i: usize| new_hashes[i] != old_hashes[i];
+        let start = (0..new_hashes.len()).find(|&i| changed(i))?;
+        let end = (0..new_hashes.len()).rfind(|&i| changed(i))? + 1;
+        let len = end - start;
+        if len < 2 {
+            return None;
+        }
+
+        let mut best: Option<(usize, i32)> = None;
+        for n in 1..len {
+            let up = (start..end - n)
+                .filter(|&i| changed(i) && new_hashes[i] == old_hashes[i + n])
+                .count();
+            let down = (start + n..end)
+                .filter(|&i| changed(i) && new_hashes[i] == old_hashes[i - n])
+                .count();
+
+            for (saved, amount) in [(up, n as i32), (down, -(n as i32))] {
+                if saved > best.map_or(0, |(s, _)| s) {
+                    best = Some((saved, amount));
+                }
+            }
+        }
+
+        let (saved, amount) = best?;
+        if saved * 2 < len {
+            return None;
+        }
+
+        Some(Scroll {
+            top: first + start as u16,
+            bottom: first + end as u16,
+            left: area.x,
+            right: area.right(),
+            amount,
+        })
+    }
+
+    /// Moves the rows of a scroll region, mirroring what the terminal does
+    /// when it receives the matching scroll sequence.
+    ///
+    /// Cells exposed by the scroll are filled with default cells, and every
+    /// row in the region is marked dirty. Columns outside the region are
+    /// left untouched.
+    pub fn scroll_rows(&mut self, scroll: Scroll) {
+        let top = scroll.top.min(self.height) as usize;
+        let bottom = scroll.bottom.min(self.height) as usize;
+        let left = scroll.left.min(self.width) as usize;
+        let right = scroll.right.min(self.width) as usize;
+        if top >= bottom || left >= right {
+            return;
+        }
+        let width = self.width as usize;
+        let n = (scroll.amount.unsigned_abs() as usize).min(bottom - top);
+        let row = |y: usize| y * width + left..y * width + right;
+
+        if scroll.amount > 0 {
+            for y in top..bottom - n {
+                self.content.copy_within(row(y + n), y * width + left);
+            }
+            for y in bottom - n..bottom {
+                self.content[row(y)].fill(Cell::default());
+            }
+        } else {
+            for y in (top + n..bottom).rev() {
+                self.content.copy_within(row(y - n), y * width + left);
+            }
+            for y in top..top + n {
+                self.content[row(y)].fill(Cell::default());
+            }
+        }
+
+        for y in top..bottom {
+            self.mark_dirty(y as u16);
+        }
+    }
+
+    /// Helper to hash the given columns of a single row.
+    fn row_hash(&self, y: u16, columns: std::ops::Range<usize>) -> u64 {
+        let start = y as usize * self.width as usize;
+        let mut hasher = DefaultHasher::new();
+        self.content[start + columns.start..start + columns.end].hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Copies a rectangular area from another buffer into this one.
     ///
     /// This is useful for scrolling or compositing multiple buffers.
+    ///
+    /// Cells that fall outside of either buffer are skipped.
     pub fn copy_from(&mut self, source: &Buffer, src_area: Rect, dest_x: u16, dest_y: u16) {
         for x in 0..src_area.width {
             for y in 0..src_area.height {
-                let cell = source.get(src_area.x + x, src_area.y + y);
-                self.set_cell(dest_x + x, dest_y + y, *cell);
+                let (Some(sx), Some(sy)) = (src_area.x.checked_add(x), src_area.y.checked_add(y))
+                else {
+                    continue;
+                };
+                let (Some(dx), Some(dy)) = (dest_x.checked_add(x), dest_y.checked_add(y)) else {
+                    continue;
+                };
+                if sx >= source.width || sy >= source.height {
+                    continue;
+                }
+                self.set_cell(dx, dy, *source.get(sx, sy));
             }
         }
     }
 }
 
+/// Helper to fingerprint a cell at column `x` of a row.
+///
+/// Blank cells contribute nothing, so a freshly reset row has a fingerprint of
+/// zero without hashing its cells.
+fn cell_fingerprint(x: u16, cell: &Cell) -> u64 {
+    if *cell == Cell::default() {
+        return 0;
+    }
+    let mut hasher = DefaultHasher::new();
+    x.hash(&mut hasher);
+    cell.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +657,249 @@ mod tests {
         assert_eq!(changes.len(), 1);
         assert_eq!(changes[0].cell.style.foreground, Some(Color::Red));
     }
+
+    #[test]
+    fn test_buffer_set_marks_row_dirty() {
+        let mut buf = Buffer::new(4, 4);
+        assert_eq!(buf.dirty_rows().count(), 0);
+
+        buf.set(0, 1, 'A');
+        buf.set_style(3, 3, Style::default());
+        buf.set(10, 2, 'Z'); // out of bounds, ignored
+
+        assert_eq!(buf.dirty_rows().collect::<Vec<_>>(), vec![1, 3]);
+        assert!(buf.is_row_dirty(1));
+        assert!(!buf.is_row_dirty(2));
+        assert!(!buf.is_row_dirty(100));
+    }
+
+    #[test]
+    fn test_buffer_reset_clears_dirty_rows() {
+        let mut buf = Buffer::new(3, 3);
+        buf.set(1, 1, 'X');
+        buf.reset();
+
+        assert_eq!(buf, Buffer::new(3, 3));
+        assert_eq!(buf.dirty_rows().count(), 0);
+    }
+
+    #[test]
+    fn test_buffer_diff_dirty_only_scans_touched_rows() {
+        let previous = Buffer::new(3, 3);
+        let mut next = Buffer::new(3, 3);
+        next.set(1, 1, 'X');
+        // Bypass damage tracking: this change is invisible to `diff_dirty`.
+        next.content[0].symbol = 'Y';
+
+        let changes = next.diff_dirty(&previous);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].x, changes[0].y), (1, 1));
+
+        next.mark_dirty(0);
+        assert_eq!(next.diff_dirty(&previous).len(), 2);
+    }
+
+    #[test]
+    fn test_buffer_diff_dirty_skips_redrawn_rows() {
+        let draw = |buf: &mut Buffer, last: char| {
+            for y in 0..3 {
+                for x in 0..3 {
+                    buf.set(x, y, 'a');
+                }
+            }
+            buf.set(2, 1, last);
+        };
+        let mut previous = Buffer::new(3, 3);
+        draw(&mut previous, 'a');
+        let mut next = Buffer::new(3, 3);
+        draw(&mut next, 'b');
+
+        // Every row was rewritten, but only row 1 needs comparing.
+        assert!((0..3).all(|y| next.is_row_dirty(y)));
+        let compared: Vec<u16> = (0..3).filter(|&y| next.row_changed(&previous, y)).collect();
+        assert_eq!(compared, vec![1]);
+
+        let changes = next.diff_dirty(&previous);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].x, changes[0].y), (2, 1));
+    }
+
+    #[test]
+    fn test_buffer_fingerprint_survives_overwrites_and_reset() {
+        let previous = Buffer::new(3, 1);
+        let mut next = Buffer::new(3, 1);
+        next.set(0, 0, 'X');
+        next.set(0, 0, ' ');
+        // Back to blank: nothing to compare.
+        assert!(!next.row_changed(&previous, 0));
+
+        next.set_with_style(1, 0, 'Y', Style::new().fg(crate::Color::Red));
+        assert!(next.row_changed(&previous, 0));
+        next.reset();
+        assert!(!next.row_changed(&previous, 0));
+    }
+
+    #[test]
+    fn test_buffer_diff_dirty_detects_cleared_rows() {
+        // Row 2 was drawn last frame but not this one: it must be erased.
+        let mut previous = Buffer::new(3, 3);
+        previous.set(0, 2, 'P');
+        let next = Buffer::new(3, 3);
+
+        let changes = next.diff_dirty(&previous);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].cell.symbol, ' ');
+        assert_eq!((changes[0].x, changes[0].y), (0, 2));
+    }
+
+    fn log_buffer(lines: &[char]) -> Buffer {
+        let mut buf = Buffer::new(2, lines.len() as u16);
+        for (y, c) in lines.iter().enumerate() {
+            buf.set(0, y as u16, *c);
+            buf.set(1, y as u16, *c);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_buffer_detect_scroll_up() {
+        let previous = log_buffer(&['a', 'b', 'c', 'd']);
+        let next = log_buffer(&['b', 'c', 'd', 'e']);
+
+        assert_eq!(
+            next.detect_scroll(&previous),
+            Some(Scroll {
+                top: 0,
+                bottom: 4,
+                left: 0,
+                right: 2,
+                amount: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_buffer_detect_scroll_down_within_band() {
+        // Header and footer stay put; only the middle band moves down by 2.
+        let previous = log_buffer(&['H', '1', '2', '3', '4', '5', 'F']);
+        let next = log_buffer(&['H', 'x', 'y', '1', '2', '3', 'F']);
+
+        assert_eq!(
+            next.detect_scroll(&previous),
+            Some(Scroll {
+                top: 1,
+                bottom: 6,
+                left: 0,
+                right: 2,
+                amount: -2
+            })
+        );
+    }
+
+    #[test]
+    fn test_buffer_detect_scroll_none_for_unrelated_rows() {
+        let previous = log_buffer(&['a', 'b', 'c', 'd']);
+        let next = log_buffer(&['w', 'x', 'y', 'z']);
+        assert_eq!(next.detect_scroll(&previous), None);
+
+        let single = log_buffer(&['a', 'b', 'X', 'd']);
+        assert_eq!(single.detect_scroll(&previous), None);
+    }
+
+    #[test]
+    fn test_buffer_scroll_rows() {
+        let mut buf = log_buffer(&['a', 'b', 'c', 'd']);
+        buf.clear_dirty();
+        buf.scroll_rows(Scroll {
+            top: 1,
+            bottom: 4,
+            left: 0,
+            right: 2,
+            amount: 1,
+        });
+        assert_eq!(buf, log_buffer(&['a', 'c', 'd', ' ']));
+        assert_eq!(buf.dirty_rows().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        buf.scroll_rows(Scroll {
+            top: 0,
+            bottom: 4,
+            left: 0,
+            right: 2,
+            amount: -2,
+        });
+        assert_eq!(buf.get(0, 0).symbol, ' ');
+        assert_eq!(buf.get(0, 1).symbol, ' ');
+        assert_eq!(buf.get(0, 2).symbol, 'a');
+        assert_eq!(buf.get(0, 3).symbol, 'c');
+    }
+
+    /// Helper to build a buffer with a static sidebar in column 0 and a log
+    /// pane in columns 1 and 2.
+    fn pane_buffer(lines: &[char]) -> Buffer {
+        let mut buf = Buffer::new(3, lines.len() as u16);
+        for (y, c) in lines.iter().enumerate() {
+            buf.set(0, y as u16, char::from(b'0' + y as u8));
+            buf.set(1, y as u16, *c);
+            buf.set(2, y as u16, *c);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_buffer_detect_scroll_next_to_sidebar() {
+        let previous = pane_buffer(&['a', 'b', 'c', 'd']);
+        let next = pane_buffer(&['b', 'c', 'd', 'e']);
+
+        // Full-width rows don't match because of the sidebar.
+        assert_eq!(next.detect_scroll(&previous), None);
+
+        let area = next.changed_area(&previous).unwrap();
+        assert_eq!(area, Rect::new(1, 0, 2, 4));
+        let scroll = next.detect_scroll_in(&previous, area).unwrap();
+        assert_eq!(
+            scroll,
+            Scroll {
+                top: 0,
+                bottom: 4,
+                left: 1,
+                right: 3,
+                amount: 1
+            }
+        );
+
+        let mut moved = previous.clone();
+        moved.scroll_rows(scroll);
+        assert_eq!(moved.diff_dirty(&next).len(), 2);
+        assert_eq!(moved.get(0, 3).symbol, '3');
+        assert_eq!(moved.get(1, 3).symbol, ' ');
+    }
+
+    #[test]
+    fn test_buffer_large_dimensions() {
+        // 300 * 300 = 90_000 cells, which does not fit in a u16.
+        let mut buf = Buffer::new(300, 300);
+        assert_eq!(buf.content.len(), 90_000);
+
+        buf.set(299, 299, 'Z');
+        assert_eq!(buf.get(299, 299).symbol, 'Z');
+        assert_eq!(buf.content[89_999].symbol, 'Z');
+
+        let changes = buf.diff(&Buffer::new(300, 300));
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].x, changes[0].y), (299, 299));
+    }
+
+    #[test]
+    fn test_buffer_copy_from_out_of_bounds() {
+        let mut source = Buffer::new(3, 3);
+        source.set(2, 2, 'S');
+
+        let mut dest = Buffer::new(3, 3);
+        // Source area extends past the source buffer and the destination
+        // offset is close to u16::MAX; nothing should panic.
+        dest.copy_from(&source, Rect::new(1, 1, 10, 10), 0, 0);
+        dest.copy_from(&source, Rect::new(0, 0, 3, 3), u16::MAX - 1, 0);
+
+        assert_eq!(dest.get(1, 1).symbol, 'S');
+    }
 }
@@ -0,0 +1,464 @@
+//! The `color` module provides color math for [`Color`].
+//!
+//! * Resolving named and indexed colors to RGB through a [`Palette`].
+//! * Conversions to and from [`Hsl`] and [`Hsv`].
+//! * Adjustments (`lighten`, `darken`, `saturate`) and alpha blending.
+//! * WCAG contrast ratios, to check that text stays readable.
+//! * [`Gradient`]s interpolated across any number of stops.
+//!
+//! Every operation that produces a new color returns [`Color::Rgb`].
+//! [`Color::Reset`] has no RGB value: operations leave it unchanged.
+//!
+//! # Example
+//! ```
+//! use phosphor::Color;
+//!
+//! let accent = Color::Rgb(40, 120, 200);
+//! let hover = accent.lighten(0.1);
+//! let disabled = accent.desaturate(0.5).blend(Color::Black, 0.3);
+//! assert_ne!(hover, accent);
+//! assert!(Color::BrightWhite.contrast_ratio(Color::Black).unwrap() > 20.0);
+//! # let _ = disabled;
+//! ```
+
+use crate::Color;
+
+/// The RGB values of the 16 named colors, used to resolve them to RGB.
+///
+/// The default matches xterm's palette. Terminals are free to use another
+/// one, so results for named colors are approximations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// Black, Red, Green, Yellow, Blue, Magenta, Cyan, White, then the
+    /// bright variants in the same order.
+    pub colors: [(u8, u8, u8); 16],
+}
+
+/// The xterm palette.
+pub const XTERM_PALETTE: Palette = Palette {
+    colors: [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ],
+};
+
+impl Default for Palette {
+    fn default() -> Self {
+        XTERM_PALETTE
+    }
+}
+
+impl Palette {
+    /// Resolves a color to RGB.
+    ///
+    /// Indexes `16-231` map to the 6x6x6 color cube and `232-255` to the
+    /// grayscale ramp. Returns `None` for [`Color::Reset`].
+    pub fn resolve(&self, color: Color) -> Option<(u8, u8, u8)> {
+        let index = match color {
+            Color::Reset => return None,
+            Color::Rgb(r, g, b) => return Some((r, g, b)),
+            Color::Indexed(i) => i,
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+        };
+
+        Some(match index {
+            0..=15 => self.colors[index as usize],
+            16..=231 => {
+                let i = index - 16;
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                (level(i / 36), level((i / 6) % 6), level(i % 6))
+            }
+            _ => {
+                let gray = 8 + (index - 232) * 10;
+                (gray, gray, gray)
+            }
+        })
+    }
+}
+
+/// A color in the HSL space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees, `0.0..360.0`.
+    pub h: f32,
+    /// Saturation, `0.0..=1.0`.
+    pub s: f32,
+    /// Lightness, `0.0..=1.0`.
+    pub l: f32,
+}
+
+/// A color in the HSV space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    /// Hue in degrees, `0.0..360.0`.
+    pub h: f32,
+    /// Saturation, `0.0..=1.0`.
+    pub s: f32,
+    /// Value, `0.0..=1.0`.
+    pub v: f32,
+}
+
+impl Color {
+    /// Resolves the color to RGB with the default [`Palette`].
+    pub fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        XTERM_PALETTE.resolve(self)
+    }
+
+    /// Converts the color to HSL.
+    pub fn to_hsl(self) -> Option<Hsl> {
+        let (r, g, b) = self.to_unit_rgb()?;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        let s = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Some(Hsl {
+            h: hue(r, g, b, max, delta),
+            s: s.clamp(0.0, 1.0),
+            l,
+        })
+    }
+
+    /// Converts the color to HSV.
+    pub fn to_hsv(self) -> Option<Hsv> {
+        let (r, g, b) = self.to_unit_rgb()?;
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        Some(Hsv {
+            h: hue(r, g, b, max, delta),
+            s,
+            v: max,
+        })
+    }
+
+    /// Creates an RGB color from HSL components.
+    ///
+    /// Out of range values are clamped and the hue wraps around.
+    pub fn from_hsl(hsl: Hsl) -> Color {
+        let s = hsl.s.clamp(0.0, 1.0);
+        let l = hsl.l.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        from_chroma(hsl.h, c, l - c / 2.0)
+    }
+
+    /// Creates an RGB color from HSV components.
+    ///
+    /// Out of range values are clamped and the hue wraps around.
+    pub fn from_hsv(hsv: Hsv) -> Color {
+        let s = hsv.s.clamp(0.0, 1.0);
+        let v = hsv.v.clamp(0.0, 1.0);
+        let c = v * s;
+        from_chroma(hsv.h, c, v - c)
+    }
+
+    /// Increases the HSL lightness by `amount` (`0.0..=1.0`).
+    pub fn lighten(self, amount: f32) -> Color {
+        self.map_hsl(|hsl| hsl.l += amount)
+    }
+
+    /// Decreases the HSL lightness by `amount` (`0.0..=1.0`).
+    pub fn darken(self, amount: f32) -> Color {
+        self.map_hsl(|hsl| hsl.l -= amount)
+    }
+
+    /// Increases the HSL saturation by `amount` (`0.0..=1.0`).
+    pub fn saturate(self, amount: f32) -> Color {
+        self.map_hsl(|hsl| hsl.s += amount)
+    }
+
+    /// Decreases the HSL saturation by `amount` (`0.0..=1.0`).
+    pub fn desaturate(self, amount: f32) -> Color {
+        self.map_hsl(|hsl| hsl.s -= amount)
+    }
+
+    /// Blends `other` over this color with the given opacity.
+    ///
+    /// `alpha` is clamped to `0.0..=1.0`: `0.0` returns this color and `1.0`
+    /// returns `other`. If either color is [`Color::Reset`], the nearest of
+    /// the two is returned unchanged.
+    pub fn blend(self, other: Color, alpha: f32) -> Color {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let (Some(a), Some(b)) = (self.to_rgb(), other.to_rgb()) else {
+            return if alpha < 0.5 { self } else { other };
+        };
+        let mix = |x: u8, y: u8| lerp(x, y, alpha);
+        Color::Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+    }
+
+    /// Returns the relative luminance as defined by WCAG 2, from `0.0`
+    /// (black) to `1.0` (white).
+    pub fn luminance(self) -> Option<f32> {
+        let (r, g, b) = self.to_unit_rgb()?;
+        let linear = |c: f32| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+    }
+
+    /// Returns the WCAG contrast ratio between two colors, from `1.0` to
+    /// `21.0`. WCAG AA asks for at least `4.5` for body text.
+    pub fn contrast_ratio(self, other: Color) -> Option<f32> {
+        let a = self.luminance()?;
+        let b = other.luminance()?;
+        let (light, dark) = if a > b { (a, b) } else { (b, a) };
+        Some((light + 0.05) / (dark + 0.05))
+    }
+
+    /// Helper to resolve the color to RGB components in `0.0..=1.0`.
+    fn to_unit_rgb(self) -> Option<(f32, f32, f32)> {
+        let (r, g, b) = self.to_rgb()?;
+        Some((r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0))
+    }
+
+    /// Helper to adjust the color in HSL space.
+    fn map_hsl<F: FnOnce(&mut Hsl)>(self, f: F) -> Color {
+        match self.to_hsl() {
+            Some(mut hsl) => {
+                f(&mut hsl);
+                Color::from_hsl(hsl)
+            }
+            None => self,
+        }
+    }
+}
+
+/// A smooth transition between colors.
+///
+/// The stops are evenly spaced: the first is at `0.0`, the last at `1.0`.
+///
+/// # Example
+/// ```
+/// use phosphor::{Color, color::Gradient};
+///
+/// let gradient = Gradient::new(vec![Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 0)]);
+/// assert_eq!(gradient.at(0.5), Color::Rgb(100, 50, 0));
+/// assert_eq!(gradient.colors(3).len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Gradient {
+    stops: Vec<Color>,
+}
+
+impl Gradient {
+    /// Creates a gradient through the given stops.
+    pub fn new(stops: Vec<Color>) -> Self {
+        Self { stops }
+    }
+
+    /// Returns the stops of the gradient.
+    pub fn stops(&self) -> &[Color] {
+        &self.stops
+    }
+
+    /// Returns the color at position `t` (clamped to `0.0..=1.0`).
+    ///
+    /// An empty gradient returns [`Color::Reset`].
+    pub fn at(&self, t: f32) -> Color {
+        let Some(&last) = self.stops.last() else {
+            return Color::Reset;
+        };
+        let segments = self.stops.len() - 1;
+        if segments == 0 {
+            return last;
+        }
+
+        let scaled = t.clamp(0.0, 1.0) * segments as f32;
+        let index = (scaled.floor() as usize).min(segments - 1);
+        self.stops[index].blend(self.stops[index + 1], scaled - index as f32)
+    }
+
+    /// Samples `n` evenly spaced colors, including both ends.
+    pub fn colors(&self, n: usize) -> Vec<Color> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![self.at(0.0)],
+            _ => (0..n).map(|i| self.at(i as f32 / (n - 1) as f32)).collect(),
+        }
+    }
+}
+
+/// Helper to compute the hue in degrees from unit RGB components.
+fn hue(r: f32, g: f32, b: f32, max: f32, delta: f32) -> f32 {
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let h = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    h * 60.0
+}
+
+/// Helper to build an RGB color from a hue, chroma and lightness offset.
+fn from_chroma(h: f32, c: f32, m: f32) -> Color {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+/// Helper to interpolate between two channel values.
+fn lerp(a: u8, b: u8, t: f32) -> u8 {
+    (a as f32 + (b as f32 - a as f32) * t).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_resolves_named_and_indexed() {
+        assert_eq!(Color::Red.to_rgb(), Some((205, 0, 0)));
+        assert_eq!(Color::Indexed(9).to_rgb(), Color::BrightRed.to_rgb());
+        assert_eq!(Color::Indexed(16).to_rgb(), Some((0, 0, 0)));
+        assert_eq!(Color::Indexed(196).to_rgb(), Some((255, 0, 0)));
+        assert_eq!(Color::Indexed(231).to_rgb(), Some((255, 255, 255)));
+        assert_eq!(Color::Indexed(232).to_rgb(), Some((8, 8, 8)));
+        assert_eq!(Color::Indexed(255).to_rgb(), Some((238, 238, 238)));
+        assert_eq!(Color::Reset.to_rgb(), None);
+    }
+
+    #[test]
+    fn test_hsl_hsv_round_trip() {
+        let hsl = Color::Rgb(255, 0, 0).to_hsl().unwrap();
+        assert_eq!((hsl.h, hsl.s, hsl.l), (0.0, 1.0, 0.5));
+
+        let hsv = Color::Rgb(0, 0, 255).to_hsv().unwrap();
+        assert_eq!((hsv.h, hsv.s, hsv.v), (240.0, 1.0, 1.0));
+
+        // Every channel survives a round-trip through both spaces.
+        for r in (0..=255).step_by(51) {
+            for g in (0..=255).step_by(51) {
+                for b in (0..=255).step_by(17) {
+                    let color = Color::Rgb(r, g, b);
+                    assert_eq!(Color::from_hsl(color.to_hsl().unwrap()), color);
+                    assert_eq!(Color::from_hsv(color.to_hsv().unwrap()), color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lighten_darken_saturate() {
+        let gray = Color::Rgb(128, 128, 128);
+        assert_eq!(gray.lighten(1.0), Color::Rgb(255, 255, 255));
+        assert_eq!(gray.darken(1.0), Color::Rgb(0, 0, 0));
+        assert_eq!(gray.saturate(0.5), gray.saturate(0.5).saturate(0.0));
+        assert_ne!(gray.saturate(0.5), gray);
+
+        let red = Color::Rgb(200, 50, 50);
+        assert_eq!(red.desaturate(1.0), Color::Rgb(125, 125, 125));
+        assert_eq!(Color::Reset.lighten(0.5), Color::Reset);
+    }
+
+    #[test]
+    fn test_blend() {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+        assert_eq!(black.blend(white, 0.0), black);
+        assert_eq!(black.blend(white, 1.0), white);
+        assert_eq!(black.blend(white, 0.5), Color::Rgb(128, 128, 128));
+        assert_eq!(black.blend(white, 7.0), white);
+        assert_eq!(Color::Reset.blend(white, 0.2), Color::Reset);
+        assert_eq!(Color::Reset.blend(white, 0.8), white);
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = Color::Rgb(0, 0, 0)
+            .contrast_ratio(Color::Rgb(255, 255, 255))
+            .unwrap();
+        assert!((ratio - 21.0).abs() < 0.01);
+        assert_eq!(Color::Blue.contrast_ratio(Color::Blue), Some(1.0));
+        // Order does not matter.
+        assert_eq!(
+            Color::Red.contrast_ratio(Color::White),
+            Color::White.contrast_ratio(Color::Red)
+        );
+        assert_eq!(Color::Reset.contrast_ratio(Color::White), None);
+    }
+
+    #[test]
+    fn test_gradient() {
+        let gradient = Gradient::new(vec![
+            Color::Rgb(0, 0, 0),
+            Color::Rgb(100, 0, 0),
+            Color::Rgb(100, 200, 0),
+        ]);
+        assert_eq!(gradient.at(0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(gradient.at(0.25), Color::Rgb(50, 0, 0));
+        assert_eq!(gradient.at(0.5), Color::Rgb(100, 0, 0));
+        assert_eq!(gradient.at(0.75), Color::Rgb(100, 100, 0));
+        assert_eq!(gradient.at(1.0), Color::Rgb(100, 200, 0));
+        assert_eq!(gradient.at(-1.0), Color::Rgb(0, 0, 0));
+
+        assert_eq!(
+            gradient.colors(5),
+            vec![
+                Color::Rgb(0, 0, 0),
+                Color::Rgb(50, 0, 0),
+                Color::Rgb(100, 0, 0),
+                Color::Rgb(100, 100, 0),
+                Color::Rgb(100, 200, 0),
+            ]
+        );
+        assert!(
+            Gradient::default()
+                .colors(3)
+                .iter()
+                .all(|c| *c == Color::Reset)
+        );
+        assert_eq!(Gradient::new(vec![Color::Red]).at(0.7), Color::Red);
+    }
+}
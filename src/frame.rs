@@ -4,13 +4,69 @@
 //! for drawing text, shapes, and widgets without having to manipulate
 //! individual cells manually.
 
-use crate::{Buffer, Rect, Style, Widget};
+use crate::text::{Alignment, char_width};
+use crate::{
+    Buffer, HitMap, Line, Rect, Role, Span, StatefulWidget, Style, StyledText, Theme, Widget,
+    WidgetRef,
+};
+
+/// The shape of the terminal cursor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorShape {
+    /// A block covering the whole cell.
+    #[default]
+    Block,
+    /// A vertical bar before the cell, as used by most text editors.
+    Bar,
+    /// A line under the cell.
+    Underline,
+}
+
+/// The terminal cursor a frame asks to display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    /// The absolute column of the cursor.
+    pub x: u16,
+    /// The absolute row of the cursor.
+    pub y: u16,
+    /// The shape of the cursor.
+    pub shape: CursorShape,
+    /// Whether the cursor blinks.
+    pub blinking: bool,
+}
+
+impl Cursor {
+    /// Creates a blinking block cursor at the given absolute position.
+    pub fn new(x: u16, y: u16) -> Self {
+        Self {
+            x,
+            y,
+            shape: CursorShape::default(),
+            blinking: true,
+        }
+    }
+
+    /// Returns the DECSCUSR sequence selecting the shape of the cursor.
+    pub fn shape_ansi(&self) -> String {
+        let steady = u8::from(!self.blinking);
+        let code = match self.shape {
+            CursorShape::Block => 1,
+            CursorShape::Underline => 3,
+            CursorShape::Bar => 5,
+        } + steady;
+        format!("\x1b[{code} q")
+    }
+}
 
 /// A high-level handle for drawing to a buffer.
 pub struct Frame<'a> {
     buffer: &'a mut Buffer,
     area: Rect,
     current_style: Style,
+    hit_map: HitMap,
+    theme: Theme,
+    cursor: Option<Cursor>,
+    cursor_shape: (CursorShape, bool),
 }
 
 impl<'a> Frame<'a> {
@@ -20,6 +76,10 @@ impl<'a> Frame<'a> {
             buffer,
             area,
             current_style: Style::default(),
+            hit_map: HitMap::new(),
+            theme: Theme::default(),
+            cursor: None,
+            cursor_shape: (CursorShape::default(), true),
         }
     }
 
@@ -37,6 +97,21 @@ impl<'a> Frame<'a> {
         self.area
     }
 
+    /// Returns the theme widgets consult for their default styles.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets the theme for all subsequent drawing operations.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Returns the style of a role in the current theme.
+    pub fn role_style(&self, role: Role) -> Style {
+        self.theme.style(role)
+    }
+
     /// Returns a mutable reference to the underlying buffer.
     ///
     /// This is an advanced API used for implementing widgets that need direct
@@ -57,22 +132,194 @@ impl<'a> Frame<'a> {
             buffer: self.buffer,
             current_style: self.current_style,
             area,
+            hit_map: std::mem::take(&mut self.hit_map),
+            theme: self.theme,
+            cursor: self.cursor,
+            cursor_shape: self.cursor_shape,
         };
         f(&mut sub_frame);
+        self.hit_map = sub_frame.hit_map;
+        self.cursor = sub_frame.cursor;
+        self.cursor_shape = sub_frame.cursor_shape;
+    }
+
+    /// Shows the terminal cursor at the given absolute coordinates once the
+    /// frame is drawn, e.g. at the caret of a text field.
+    ///
+    /// The cursor stays hidden unless a frame requests it, and the last
+    /// request wins. Positions outside the buffer hide it.
+    pub fn set_cursor_position(&mut self, x: u16, y: u16) {
+        let (shape, blinking) = self.cursor_shape;
+        self.cursor = Some(Cursor {
+            x,
+            y,
+            shape,
+            blinking,
+        });
+    }
+
+    /// Sets the shape of the terminal cursor, for this and later calls to
+    /// [`Frame::set_cursor_position`].
+    pub fn set_cursor_shape(&mut self, shape: CursorShape, blinking: bool) {
+        self.cursor_shape = (shape, blinking);
+        if let Some(cursor) = &mut self.cursor {
+            cursor.shape = shape;
+            cursor.blinking = blinking;
+        }
+    }
+
+    /// Returns the cursor requested so far, if any.
+    pub fn cursor(&self) -> Option<Cursor> {
+        self.cursor
+            .filter(|c| c.x < self.buffer.width && c.y < self.buffer.height)
+    }
+
+    /// Registers a mouse hit region for the given absolute area.
+    ///
+    /// The region is placed at z-order `0`; regions registered later are
+    /// considered on top of earlier ones. See [`HitMap`] for details.
+    pub fn register_hit<S: Into<String>>(&mut self, area: Rect, id: S) {
+        self.register_hit_z(area, id, 0);
+    }
+
+    /// Registers a mouse hit region with an explicit z-order.
+    ///
+    /// Use a higher `z` for overlays such as modals and popups so they win
+    /// over the content below, regardless of drawing order. The area is
+    /// clipped to the underlying buffer.
+    pub fn register_hit_z<S: Into<String>>(&mut self, area: Rect, id: S, z: i16) {
+        let bounds = Rect::new(0, 0, self.buffer.width, self.buffer.height);
+        self.hit_map.register(area.intersection(bounds), id, z);
+    }
+
+    /// Returns the hit regions registered so far.
+    pub fn hit_map(&self) -> &HitMap {
+        &self.hit_map
+    }
+
+    /// Consumes the frame and returns the hit regions registered during drawing.
+    pub fn into_hit_map(self) -> HitMap {
+        self.hit_map
     }
 
     /// Writes a string to the buffer starting at the given coordinates.
     ///
-    /// Text that exceeds the buffer width will be clipped.
+    /// Text that exceeds the frame's area will be clipped. Wide characters
+    /// occupy two cells and zero-width characters are skipped.
     pub fn write_str(&mut self, x: u16, y: u16, text: &str) {
-        for (i, c) in text.chars().enumerate() {
-            self.buffer.set_with_style(
-                self.area.x + x + (i as u16),
-                self.area.y + y,
-                c,
-                self.current_style,
-            );
+        self.put_str(x, y, text, self.current_style, usize::MAX);
+    }
+
+    /// Writes a span at the given coordinates, using the span's style.
+    ///
+    /// The span's style is patched over the frame's current style, so unset
+    /// colors and modifiers are inherited.
+    ///
+    /// Returns the number of columns written.
+    pub fn write_span(&mut self, x: u16, y: u16, span: &Span) -> u16 {
+        let style = self.span_style(span);
+        self.put_str(x, y, &span.content, style, usize::MAX) as u16
+    }
+
+    /// Writes the spans of a line one after the other, starting at the given
+    /// coordinates. The alignment of the line is ignored.
+    ///
+    /// Returns the number of columns written.
+    pub fn write_line(&mut self, x: u16, y: u16, line: &Line) -> u16 {
+        self.put_line(x, y, line, usize::MAX) as u16
+    }
+
+    /// Draws a line on the first row of the given absolute area.
+    ///
+    /// The line is aligned within the area (left-aligned if it has no
+    /// alignment) and clipped to the area's width.
+    pub fn render_line(&mut self, line: &Line, area: Rect) {
+        let alignment = line.alignment.unwrap_or_default();
+        self.render_line_aligned(line, area, alignment);
+    }
+
+    /// Draws multi-line text into the given absolute area, one line per row.
+    ///
+    /// Lines that don't fit in the area are clipped.
+    pub fn render_text(&mut self, text: &StyledText, area: Rect) {
+        for (line, row) in text.lines.iter().zip(area.rows()) {
+            self.render_line(line, row);
+        }
+    }
+
+    /// Draws a line aligned within the first row of an absolute area, using
+    /// `alignment` when the line doesn't specify its own.
+    pub(crate) fn render_line_aligned(&mut self, line: &Line, area: Rect, alignment: Alignment) {
+        if area.is_empty() {
+            return;
+        }
+        let available = area.width as usize;
+        let alignment = line.alignment.unwrap_or(alignment);
+        let offset = alignment.offset(line.width(), available) as u16;
+
+        self.render_area(area, |f| {
+            f.put_line(offset, 0, line, available - offset as usize);
+        });
+    }
+
+    /// Helper to draw the spans of a line, stopping once `max_width`
+    /// columns have been used.
+    fn put_line(&mut self, x: u16, y: u16, line: &Line, max_width: usize) -> usize {
+        let mut used = 0;
+        for span in &line.spans {
+            let Ok(span_x) = u16::try_from(x as usize + used) else {
+                break;
+            };
+            let style = self.span_style(span);
+            used += self.put_str(span_x, y, &span.content, style, max_width - used);
         }
+        used
+    }
+
+    /// Helper to pick the style a span is drawn with.
+    fn span_style(&self, span: &Span) -> Style {
+        self.current_style.patch(span.style)
+    }
+
+    /// Helper to draw a string relative to the frame, stopping before the
+    /// first character that would exceed `max_width` columns or the frame's
+    /// area. A wide character that only half fits is dropped.
+    ///
+    /// Returns the number of columns written.
+    fn put_str(&mut self, x: u16, y: u16, text: &str, style: Style, max_width: usize) -> usize {
+        if y >= self.area.height {
+            return 0;
+        }
+        let max_width = max_width.min(self.area.width.saturating_sub(x) as usize);
+        let Ok(abs_y) = u16::try_from(self.area.y as usize + y as usize) else {
+            return 0;
+        };
+        let start_x = self.area.x as usize + x as usize;
+        let mut used = 0;
+
+        for c in text.chars() {
+            let width = char_width(c);
+            if width == 0 {
+                continue;
+            }
+            if used + width > max_width {
+                break;
+            }
+            let Ok(abs_x) = u16::try_from(start_x + used) else {
+                break;
+            };
+            self.buffer.set_with_style(abs_x, abs_y, c, style);
+            // The right half of a wide character is covered by a blank cell
+            // which the renderer skips.
+            if width == 2
+                && let Some(next_x) = abs_x.checked_add(1)
+            {
+                self.buffer.set_with_style(next_x, abs_y, ' ', style);
+            }
+            used += width;
+        }
+
+        used
     }
 
     /// Sets the style to be used for all subsequent drawing operations.
@@ -85,16 +332,18 @@ impl<'a> Frame<'a> {
         self.current_style = Style::default()
     }
 
-    /// Executes a closure with a specific style, then restores the previous style.
+    /// Executes a closure with a style patched over the current one, then
+    /// restores the previous style.
     ///
     /// This is useful for drawing a specific section of the UI with a different
-    /// style without affecting subsequent drawing operations.
+    /// style without affecting subsequent drawing operations. Colors and
+    /// modifiers not set by `style` are inherited (see [`Style::patch`]).
     pub fn with_style<F>(&mut self, style: Style, f: F)
     where
         F: FnOnce(&mut Frame),
     {
         let old_style = self.current_style;
-        self.current_style = style;
+        self.current_style = old_style.patch(style);
         f(self);
         self.current_style = old_style;
     }
@@ -103,6 +352,22 @@ impl<'a> Frame<'a> {
         widget.render(area, self);
     }
 
+    /// Renders a stateful widget into the given area of the frame, letting
+    /// it update `state`.
+    pub fn render_stateful_widget<W: StatefulWidget>(
+        &mut self,
+        widget: W,
+        area: Rect,
+        state: &mut W::State,
+    ) {
+        widget.render(area, self, state);
+    }
+
+    /// Renders a widget by reference into the given area of the frame.
+    pub fn render_widget_ref<W: WidgetRef + ?Sized>(&mut self, widget: &W, area: Rect) {
+        widget.render_ref(area, self);
+    }
+
     /// Writes a string to the buffer starting at the given coordinates using a specific style.
     ///
     /// This is a convenience method that temporarily sets the style, writes the string,
@@ -117,9 +382,9 @@ impl<'a> Frame<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Color;
     use crate::buffer::Buffer;
     use crate::widgets::Text;
+    use crate::{Color, Modifier};
 
     #[test]
     fn test_frame_render_widget() {
@@ -132,6 +397,19 @@ mod tests {
         assert_eq!(buffer.get(0, 0).symbol, 'W');
     }
 
+    #[test]
+    fn test_frame_render_widget_by_reference() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let text = Text::new("Hi");
+
+        frame.render_widget_ref(&text, Rect::new(0, 0, 10, 1));
+        frame.render_widget(&text, Rect::new(3, 1, 7, 1));
+
+        assert_eq!(buffer.get(0, 0).symbol, 'H');
+        assert_eq!(buffer.get(4, 1).symbol, 'i');
+    }
+
     #[test]
     fn test_frame_with_style_scoped() {
         let mut buffer = Buffer::new(10, 1);
@@ -169,6 +447,29 @@ mod tests {
         assert_eq!(buffer.get(0, 0).symbol, ' ');
     }
 
+    #[test]
+    fn test_frame_with_style_inherits() {
+        let mut buffer = Buffer::new(3, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 1));
+
+        frame.with_style(Style::new().fg(Color::Red).bg(Color::Blue), |f| {
+            f.with_style(Style::new().modifier(Modifier::BOLD), |f| {
+                f.write_str(0, 0, "a");
+            });
+            f.write_span(1, 0, &Span::styled("b", Style::new().fg(Color::Green)));
+        });
+
+        let a = buffer.get(0, 0).style;
+        assert_eq!(a.foreground, Some(Color::Red));
+        assert_eq!(a.background, Some(Color::Blue));
+        assert!(a.modifiers.contains(Modifier::BOLD));
+
+        let b = buffer.get(1, 0).style;
+        assert_eq!(b.foreground, Some(Color::Green));
+        assert_eq!(b.background, Some(Color::Blue));
+        assert!(!b.modifiers.contains(Modifier::BOLD));
+    }
+
     #[test]
     fn test_frame_styled_write_str() {
         let mut buffer = Buffer::new(10, 1);
@@ -208,4 +509,141 @@ mod tests {
         assert_eq!(buffer.get(2, 0).symbol, 'H');
         assert_eq!(buffer.get(4, 0).symbol, 'l');
     }
+
+    #[test]
+    fn test_frame_register_hit_in_sub_frame() {
+        let mut buffer = Buffer::new(20, 10);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 10));
+
+        frame.register_hit(Rect::new(0, 0, 20, 10), "root");
+        frame.render_area(Rect::new(5, 5, 5, 5), |f| {
+            f.register_hit(Rect::new(5, 5, 5, 5), "child");
+        });
+        // Regions outside the buffer are clipped.
+        frame.register_hit_z(Rect::new(15, 8, 50, 50), "overlay", 1);
+
+        let hits = frame.into_hit_map();
+        assert_eq!(hits.regions().len(), 3);
+        assert_eq!(hits.hit_test(6, 6), Some("child"));
+        assert_eq!(hits.hit_test(0, 0), Some("root"));
+        assert_eq!(hits.hit_test(19, 9), Some("overlay"));
+        assert_eq!(hits.regions()[2].area, Rect::new(15, 8, 5, 2));
+    }
+
+    #[test]
+    fn test_frame_cursor_from_sub_frame() {
+        let mut buffer = Buffer::new(10, 5);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 5));
+        assert_eq!(frame.cursor(), None);
+
+        frame.set_cursor_shape(CursorShape::Bar, false);
+        frame.render_area(Rect::new(2, 2, 5, 1), |f| f.set_cursor_position(4, 2));
+        let cursor = frame.cursor().unwrap();
+        assert_eq!((cursor.x, cursor.y, cursor.shape), (4, 2, CursorShape::Bar));
+        assert_eq!(cursor.shape_ansi(), "\x1b[6 q");
+
+        frame.set_cursor_position(10, 0);
+        assert_eq!(frame.cursor(), None);
+    }
+
+    #[test]
+    fn test_frame_write_wide_chars() {
+        let mut buffer = Buffer::new(6, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 1));
+
+        frame.write_str(0, 0, "a漢b\u{0301}");
+
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        assert_eq!(buffer.get(1, 0).symbol, '漢');
+        assert_eq!(buffer.get(2, 0).symbol, ' ');
+        assert_eq!(buffer.get(3, 0).symbol, 'b');
+        assert_eq!(buffer.get(4, 0).symbol, ' ');
+    }
+
+    #[test]
+    fn test_frame_write_str_clipped_to_sub_frame() {
+        let mut buffer = Buffer::new(6, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 2));
+        frame.write_str(0, 0, "......");
+
+        frame.render_area(Rect::new(0, 0, 3, 1), |f| {
+            f.write_str(0, 0, "ab日");
+            f.write_str(1, 0, "xyz");
+            f.write_str(0, 1, "below");
+        });
+
+        // The wide char only half fits, so it is dropped and the cell next
+        // to the area keeps its content.
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        assert_eq!(buffer.get(1, 0).symbol, 'x');
+        assert_eq!(buffer.get(2, 0).symbol, 'y');
+        assert_eq!(buffer.get(3, 0).symbol, '.');
+        assert_eq!(buffer.get(0, 1).symbol, ' ');
+    }
+
+    #[test]
+    fn test_frame_write_line_spans() {
+        let mut buffer = Buffer::new(10, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+        let line = Line::new(vec![
+            Span::styled("Err", Style::new().fg(Color::Red)),
+            Span::raw(": x"),
+        ]);
+
+        let written = frame.write_line(1, 0, &line);
+
+        assert_eq!(written, 6);
+        assert_eq!(buffer.get(1, 0).symbol, 'E');
+        assert_eq!(buffer.get(1, 0).style.foreground, Some(Color::Red));
+        assert_eq!(buffer.get(4, 0).symbol, ':');
+        assert_eq!(buffer.get(4, 0).style.foreground, None);
+    }
+
+    #[test]
+    fn test_frame_render_line_alignment_and_clipping() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+
+        frame.render_line(
+            &Line::raw("ab").alignment(Alignment::Right),
+            Rect::new(0, 0, 10, 1),
+        );
+        frame.render_line(
+            &Line::raw("abcd").alignment(Alignment::Center),
+            Rect::new(0, 1, 10, 1),
+        );
+        // A wide char that doesn't fully fit is dropped.
+        frame.render_line(&Line::raw("abc漢"), Rect::new(0, 2, 4, 1));
+
+        assert_eq!(buffer.get(8, 0).symbol, 'a');
+        assert_eq!(buffer.get(9, 0).symbol, 'b');
+        assert_eq!(buffer.get(3, 1).symbol, 'a');
+        assert_eq!(buffer.get(6, 1).symbol, 'd');
+        assert_eq!(buffer.get(2, 2).symbol, 'c');
+        assert_eq!(buffer.get(3, 2).symbol, ' ');
+    }
+
+    #[test]
+    fn test_frame_render_text() {
+        let mut buffer = Buffer::new(5, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 2));
+
+        frame.render_text(&StyledText::raw("one\ntwo\nthree"), Rect::new(1, 0, 4, 2));
+
+        assert_eq!(buffer.get(1, 0).symbol, 'o');
+        assert_eq!(buffer.get(1, 1).symbol, 't');
+        assert_eq!(buffer.get(3, 1).symbol, 'o');
+    }
+
+    #[test]
+    fn test_frame_write_str_extreme_offsets() {
+        let mut buffer = Buffer::new(5, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(u16::MAX - 2, u16::MAX, 5, 1));
+
+        // Coordinates past u16::MAX must be dropped, not wrap around.
+        frame.write_str(0, 0, "Hello");
+        frame.write_str(u16::MAX, u16::MAX, "World");
+
+        assert!(buffer.content.iter().all(|c| c.symbol == ' '));
+    }
 }
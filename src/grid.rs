@@ -0,0 +1,238 @@
+//! The `grid` module provides a two-dimensional layout.
+//!
+//! A [`Grid`] splits an area into rows and columns, each sized with the same
+//! [`Constraint`]s as a [`Layout`]. The result is a [`GridAreas`], from which
+//! single cells or spans of several rows and columns can be looked up.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Constraint, Grid, Rect};
+//!
+//! let areas = Grid::new(
+//!     vec![Constraint::Length(3), Constraint::Fill],
+//!     vec![Constraint::Length(20), Constraint::Fill],
+//! )
+//! .column_gutter(1)
+//! .split(Rect::new(0, 0, 80, 24));
+//!
+//! let header = areas.span(0, 0, 1, 2);
+//! let sidebar = areas.cell(1, 0);
+//! let content = areas.cell(1, 1);
+//!
+//! assert_eq!(header, Rect::new(0, 0, 80, 3));
+//! assert_eq!(sidebar, Rect::new(0, 3, 20, 21));
+//! assert_eq!(content, Rect::new(21, 3, 59, 21));
+//! ```
+
+use crate::{Constraint, Direction, Layout, Margin, Rect};
+
+/// A layout that splits an area into rows and columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    /// The constraints for each row, from top to bottom.
+    pub rows: Vec<Constraint>,
+    /// The constraints for each column, from left to right.
+    pub columns: Vec<Constraint>,
+    /// The number of empty rows between two rows.
+    pub row_gutter: u16,
+    /// The number of empty columns between two columns.
+    pub column_gutter: u16,
+    /// The space removed around the area before splitting it.
+    pub margin: Margin,
+}
+
+impl Grid {
+    /// Creates a grid with the given row and column constraints.
+    pub fn new(rows: Vec<Constraint>, columns: Vec<Constraint>) -> Self {
+        Self {
+            rows,
+            columns,
+            row_gutter: 0,
+            column_gutter: 0,
+            margin: Margin::default(),
+        }
+    }
+
+    /// Sets the same gutter between rows and between columns.
+    pub fn gutter(mut self, gutter: u16) -> Self {
+        self.row_gutter = gutter;
+        self.column_gutter = gutter;
+        self
+    }
+
+    /// Sets the number of empty rows between two rows.
+    pub fn row_gutter(mut self, gutter: u16) -> Self {
+        self.row_gutter = gutter;
+        self
+    }
+
+    /// Sets the number of empty columns between two columns.
+    pub fn column_gutter(mut self, gutter: u16) -> Self {
+        self.column_gutter = gutter;
+        self
+    }
+
+    /// Sets the same margin on every side of the area.
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = Margin::uniform(margin);
+        self
+    }
+
+    /// Splits the area into cells.
+    pub fn split(&self, area: Rect) -> GridAreas {
+        let area = area.inner(self.margin);
+        let rows = Layout::new(Direction::Vertical, self.rows.clone())
+            .spacing(self.row_gutter)
+            .split(area);
+        let columns = Layout::new(Direction::Horizontal, self.columns.clone())
+            .spacing(self.column_gutter)
+            .split(area);
+
+        GridAreas {
+            rows: rows.iter().map(|r| (r.y, r.height)).collect(),
+            columns: columns.iter().map(|c| (c.x, c.width)).collect(),
+        }
+    }
+}
+
+/// The cells of a [`Grid`] split over an area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridAreas {
+    /// The `(y, height)` of each row.
+    rows: Vec<(u16, u16)>,
+    /// The `(x, width)` of each column.
+    columns: Vec<(u16, u16)>,
+}
+
+impl GridAreas {
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the area of the cell at `row` and `column`, if it exists.
+    pub fn get(&self, row: usize, column: usize) -> Option<Rect> {
+        self.get_span(row, column, 1, 1)
+    }
+
+    /// Returns the area covering `row_span` rows and `column_span` columns
+    /// from the cell at `row` and `column`, gutters included.
+    ///
+    /// Returns `None` if the span is empty or extends past the grid.
+    pub fn get_span(
+        &self,
+        row: usize,
+        column: usize,
+        row_span: usize,
+        column_span: usize,
+    ) -> Option<Rect> {
+        let (y, bottom) = extent(&self.rows, row, row_span)?;
+        let (x, right) = extent(&self.columns, column, column_span)?;
+        Some(Rect::new(x, y, right - x, bottom - y))
+    }
+
+    /// Returns the area of the cell at `row` and `column`.
+    ///
+    /// # Panics
+    /// Panics if the cell is outside the grid.
+    pub fn cell(&self, row: usize, column: usize) -> Rect {
+        self.span(row, column, 1, 1)
+    }
+
+    /// Returns the area covering `row_span` rows and `column_span` columns
+    /// from the cell at `row` and `column`, gutters included.
+    ///
+    /// # Panics
+    /// Panics if the span is empty or extends past the grid.
+    pub fn span(&self, row: usize, column: usize, row_span: usize, column_span: usize) -> Rect {
+        self.get_span(row, column, row_span, column_span)
+            .unwrap_or_else(|| {
+                panic!(
+                    "grid span ({}, {}) + ({}, {}) is outside a {}x{} grid",
+                    row,
+                    column,
+                    row_span,
+                    column_span,
+                    self.rows(),
+                    self.columns()
+                )
+            })
+    }
+
+    /// Returns an iterator over every cell in row-major order, as
+    /// `(row, column, area)`.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, Rect)> + '_ {
+        (0..self.rows()).flat_map(move |row| {
+            (0..self.columns()).map(move |column| (row, column, self.cell(row, column)))
+        })
+    }
+}
+
+/// Helper to compute the `(start, end)` covered by `span` tracks from `index`.
+fn extent(tracks: &[(u16, u16)], index: usize, span: usize) -> Option<(u16, u16)> {
+    let last = index.checked_add(span)?.checked_sub(1)?;
+    let &(start, _) = tracks.get(index)?;
+    let &(last_start, last_size) = tracks.get(last)?;
+    Some((start, last_start.saturating_add(last_size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_cells_and_gutters() {
+        let areas = Grid::new(vec![Constraint::Fill; 2], vec![Constraint::Fill; 3])
+            .gutter(1)
+            .split(Rect::new(0, 0, 11, 5));
+
+        assert_eq!((areas.rows(), areas.columns()), (2, 3));
+        assert_eq!(areas.cell(0, 0), Rect::new(0, 0, 3, 2));
+        assert_eq!(areas.cell(0, 1), Rect::new(4, 0, 3, 2));
+        assert_eq!(areas.cell(1, 2), Rect::new(8, 3, 3, 2));
+        assert_eq!(areas.iter().count(), 6);
+    }
+
+    #[test]
+    fn test_grid_spans_include_gutters() {
+        let areas = Grid::new(
+            vec![
+                Constraint::Length(2),
+                Constraint::Fill,
+                Constraint::Length(1),
+            ],
+            vec![Constraint::Ratio(1, 4), Constraint::Fill],
+        )
+        .column_gutter(2)
+        .margin(1)
+        .split(Rect::new(0, 0, 42, 12));
+
+        assert_eq!(areas.span(0, 0, 1, 2), Rect::new(1, 1, 40, 2));
+        assert_eq!(areas.span(1, 0, 2, 1), Rect::new(1, 3, 9, 8));
+        assert_eq!(areas.cell(1, 1), Rect::new(12, 3, 29, 7));
+    }
+
+    #[test]
+    fn test_grid_out_of_range() {
+        let areas = Grid::new(vec![Constraint::Fill], vec![Constraint::Fill; 2])
+            .split(Rect::new(0, 0, 10, 10));
+
+        assert_eq!(areas.get(1, 0), None);
+        assert_eq!(areas.get_span(0, 1, 1, 2), None);
+        assert_eq!(areas.get_span(0, 0, 0, 1), None);
+        assert!(areas.get_span(0, 0, 1, 2).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 1x2 grid")]
+    fn test_grid_cell_panics_outside() {
+        let areas = Grid::new(vec![Constraint::Fill], vec![Constraint::Fill; 2])
+            .split(Rect::new(0, 0, 10, 10));
+        areas.cell(0, 2);
+    }
+}
@@ -0,0 +1,139 @@
+//! The `hitmap` module maps screen regions to component ids for mouse routing.
+//!
+//! While drawing, widgets and application code register the [`Rect`]s they
+//! occupy on the [`Frame`](crate::Frame) under a string id. After the frame is
+//! rendered, the runtime keeps the resulting [`HitMap`] and uses it to resolve
+//! the raw coordinates of a [`MouseEvent`] to the topmost registered id.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Buffer, Frame, MouseEvent, MouseKind, Rect};
+//!
+//! let mut buffer = Buffer::new(20, 10);
+//! let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 10));
+//! frame.register_hit(Rect::new(0, 0, 20, 10), "background");
+//! frame.register_hit(Rect::new(5, 5, 4, 1), "ok_button");
+//!
+//! let hits = frame.into_hit_map();
+//! let click = MouseEvent::new(6, 5, MouseKind::LeftClick);
+//! assert_eq!(hits.resolve(&click), Some("ok_button"));
+//! ```
+
+use crate::{MouseEvent, Rect};
+
+/// A single region registered in a [`HitMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HitRegion {
+    /// The absolute screen area covered by the region.
+    pub area: Rect,
+    /// The id reported when the region is hit.
+    pub id: String,
+    /// The stacking order. Higher values are on top.
+    pub z: i16,
+}
+
+/// A collection of screen regions, produced while drawing a frame.
+///
+/// Regions with a higher `z` win over lower ones. When two regions share the
+/// same `z`, the one registered last wins, matching the painter's order in
+/// which later widgets are drawn over earlier ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HitMap {
+    regions: Vec<HitRegion>,
+}
+
+impl HitMap {
+    /// Creates an empty hit map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a region with the given id and stacking order.
+    ///
+    /// Empty areas are ignored.
+    pub fn register<S: Into<String>>(&mut self, area: Rect, id: S, z: i16) {
+        if area.is_empty() {
+            return;
+        }
+        self.regions.push(HitRegion {
+            area,
+            id: id.into(),
+            z,
+        });
+    }
+
+    /// Removes every region.
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Returns `true` if no region has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Returns the registered regions in registration order.
+    pub fn regions(&self) -> &[HitRegion] {
+        &self.regions
+    }
+
+    /// Returns the id of the topmost region containing `(x, y)`, if any.
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<&str> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.area.contains(x, y))
+            .max_by_key(|(i, r)| (r.z, *i))
+            .map(|(_, r)| r.id.as_str())
+    }
+
+    /// Returns the id of the topmost region under a mouse event, if any.
+    pub fn resolve(&self, event: &MouseEvent) -> Option<&str> {
+        self.hit_test(event.x, event.y)
+    }
+
+    /// Returns the topmost region under `(x, y)`, if any.
+    pub fn region_at(&self, x: u16, y: u16) -> Option<&HitRegion> {
+        let id = self.hit_test(x, y)?;
+        self.regions
+            .iter()
+            .rev()
+            .filter(|r| r.area.contains(x, y))
+            .find(|r| r.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MouseKind;
+
+    #[test]
+    fn test_hitmap_resolves_topmost_by_order() {
+        let mut hits = HitMap::new();
+        hits.register(Rect::new(0, 0, 10, 10), "back", 0);
+        hits.register(Rect::new(2, 2, 3, 3), "front", 0);
+
+        assert_eq!(hits.hit_test(3, 3), Some("front"));
+        assert_eq!(hits.hit_test(0, 0), Some("back"));
+        assert_eq!(hits.hit_test(10, 10), None);
+    }
+
+    #[test]
+    fn test_hitmap_z_order_wins_over_registration_order() {
+        let mut hits = HitMap::new();
+        hits.register(Rect::new(0, 0, 10, 10), "modal", 10);
+        hits.register(Rect::new(0, 0, 10, 10), "content", 0);
+
+        let click = MouseEvent::new(5, 5, MouseKind::LeftClick);
+        assert_eq!(hits.resolve(&click), Some("modal"));
+        assert_eq!(hits.region_at(5, 5).map(|r| r.z), Some(10));
+    }
+
+    #[test]
+    fn test_hitmap_ignores_empty_regions() {
+        let mut hits = HitMap::new();
+        hits.register(Rect::new(0, 0, 0, 5), "empty", 0);
+        assert!(hits.is_empty());
+    }
+}
@@ -51,6 +51,10 @@ pub enum MouseKind {
     MiddleClick,
     ScrollUp,
     ScrollDown,
+    /// The pointer moved (with or without a button held down).
+    ///
+    /// Only reported when the terminal has any-event mouse tracking enabled.
+    Moved,
     Other,
 }
 
@@ -197,59 +201,17 @@ impl Parser {
             }
 
             match self.buffer[0] {
-                b'\r' => {
-                    events.push(Event::Key(KeyEvent::new(KeyCode::Enter)));
-                    self.buffer.pop_front();
-                }
                 b'\x1b' => {
-                    if self.buffer.len() == 1 {
+                    let bytes = self.buffer.make_contiguous();
+                    let Some((event, len)) = parse_escape(bytes) else {
                         break; // Incomplete, wait for more data
-                    }
-
-                    if self.buffer[1] == b'[' && self.buffer.len() < 3 {
-                        break; // Incomplete CSI, wait for more data
-                    }
-
-                    if self.buffer.len() >= 3 && self.buffer[1] == b'[' {
-                        match self.buffer[2] {
-                            b'A' => {
-                                events.push(Event::Key(KeyEvent::new(KeyCode::Up)));
-                                self.consume(3);
-                            }
-                            b'M' => {
-                                if self.buffer.len() < 6 {
-                                    break;
-                                }
-                                self.consume(3);
-
-                                let cb = self.buffer.pop_front().unwrap();
-                                let cx = self.buffer.pop_front().unwrap();
-                                let cy = self.buffer.pop_front().unwrap();
-
-                                let kind = match cb.saturating_sub(32) {
-                                    0 => MouseKind::LeftClick,
-                                    1 => MouseKind::MiddleClick,
-                                    2 => MouseKind::RightClick,
-                                    64 => MouseKind::ScrollUp,
-                                    65 => MouseKind::ScrollDown,
-                                    _ => MouseKind::Other,
-                                };
-
-                                events.push(Event::Mouse(MouseEvent::new(
-                                    (cx.saturating_sub(33)) as u16,
-                                    (cy.saturating_sub(33)) as u16,
-                                    kind,
-                                )));
-                            }
-                            _ => {
-                                events.push(Event::Key(KeyEvent::new(KeyCode::Esc)));
-                                self.buffer.pop_front();
-                            }
-                        }
-                    } else {
-                        events.push(Event::Key(KeyEvent::new(KeyCode::Esc)));
-                        self.buffer.pop_front();
-                    }
+                    };
+                    events.extend(event);
+                    self.consume(len);
+                }
+                b if b < 0x80 => {
+                    events.push(Event::Key(ascii_key(b)));
+                    self.buffer.pop_front();
                 }
                 b => {
                     let width = utf8_char_width(b);
@@ -306,7 +268,126 @@ impl Parser {
     }
 }
 
-fn utf8_char_width(first_byte: u8) -> usize {
+/// Decodes a single ASCII byte, mapping control bytes to `Ctrl` + letter.
+fn ascii_key(byte: u8) -> KeyEvent {
+    let ctrl = |c: u8| KeyEvent::with_modifiers(KeyCode::Char(c as char), KeyModifiers::CTRL);
+    match byte {
+        b'\r' | b'\n' => KeyEvent::new(KeyCode::Enter),
+        b'\t' => KeyEvent::new(KeyCode::Tab),
+        0x08 | 0x7f => KeyEvent::new(KeyCode::Backspace),
+        0x00 => KeyEvent::new(KeyCode::Null),
+        0x01..=0x1a => ctrl(b'a' + byte - 1),
+        0x1c..=0x1f => ctrl(byte + 0x40),
+        _ => KeyEvent::new(KeyCode::Char(byte as char)),
+    }
+}
+
+/// Decodes the escape sequence at the start of `bytes`.
+///
+/// Returns the event, if the sequence is known, and the number of bytes it
+/// takes, or `None` if the sequence is incomplete.
+fn parse_escape(bytes: &[u8]) -> Option<(Option<Event>, usize)> {
+    let key = |code| Some(Event::Key(KeyEvent::new(code)));
+    match bytes.get(1) {
+        None => None,
+        // X10 mouse report: `ESC [ M` followed by three raw bytes.
+        Some(b'[') if bytes.get(2) == Some(&b'M') => {
+            let &[_, _, _, cb, cx, cy, ..] = bytes else {
+                return None;
+            };
+            // Bits 4, 8 and 16 report Shift, Alt and Ctrl; they are ignored.
+            let kind = match cb.saturating_sub(32) & !0b11100 {
+                0 => MouseKind::LeftClick,
+                1 => MouseKind::MiddleClick,
+                2 => MouseKind::RightClick,
+                64 => MouseKind::ScrollUp,
+                65 => MouseKind::ScrollDown,
+                32..=35 => MouseKind::Moved,
+                _ => MouseKind::Other,
+            };
+            let mouse = MouseEvent::new(
+                cx.saturating_sub(33) as u16,
+                cy.saturating_sub(33) as u16,
+                kind,
+            );
+            Some((Some(Event::Mouse(mouse)), 6))
+        }
+        // CSI: `ESC [`, parameters, then a final byte.
+        Some(b'[') => {
+            let params_len = bytes[2..]
+                .iter()
+                .take_while(|b| (0x30..=0x3f).contains(*b))
+                .count();
+            let &last = bytes.get(2 + params_len)?;
+            if !(0x40..=0x7e).contains(&last) {
+                return Some((key(KeyCode::Esc), 1));
+            }
+            let params: Vec<u16> = std::str::from_utf8(&bytes[2..2 + params_len])
+                .unwrap_or_default()
+                .split(';')
+                .map(|p| p.parse().unwrap_or(1))
+                .collect();
+            let code = match (last, params[0]) {
+                (b'A', _) => KeyCode::Up,
+                (b'B', _) => KeyCode::Down,
+                (b'C', _) => KeyCode::Right,
+                (b'D', _) => KeyCode::Left,
+                (b'H', _) | (b'~', 1 | 7) => KeyCode::Home,
+                (b'F', _) | (b'~', 4 | 8) => KeyCode::End,
+                (b'Z', _) => KeyCode::Tab,
+                (b'~', 3) => KeyCode::Delete,
+                (b'~', 5) => KeyCode::PageUp,
+                (b'~', 6) => KeyCode::PageDown,
+                (b'~', n @ 11..=15) => KeyCode::F((n - 10) as u8),
+                (b'~', n @ 17..=21) => KeyCode::F((n - 11) as u8),
+                (b'~', n @ 23..=24) => KeyCode::F((n - 12) as u8),
+                _ => return Some((None, 3 + params_len)),
+            };
+            // The second parameter is 1 + a bitmask of Shift, Alt and Ctrl.
+            let mask = params.get(1).map_or(0, |m| m.saturating_sub(1));
+            let mut modifiers = KeyModifiers::empty();
+            for (bit, modifier) in [
+                (1, KeyModifiers::SHIFT),
+                (2, KeyModifiers::ALT),
+                (4, KeyModifiers::CTRL),
+            ] {
+                if mask & bit != 0 {
+                    modifiers.insert(modifier);
+                }
+            }
+            if last == b'Z' {
+                modifiers.insert(KeyModifiers::SHIFT);
+            }
+            let event = KeyEvent::with_modifiers(code, modifiers);
+            Some((Some(Event::Key(event)), 3 + params_len))
+        }
+        // SS3: `ESC O` and a letter, sent by some terminals for arrows and F1-F4.
+        Some(b'O') => {
+            let code = match bytes.get(2)? {
+                b'A' => KeyCode::Up,
+                b'B' => KeyCode::Down,
+                b'C' => KeyCode::Right,
+                b'D' => KeyCode::Left,
+                b'H' => KeyCode::Home,
+                b'F' => KeyCode::End,
+                b @ b'P'..=b'S' => KeyCode::F(b - b'P' + 1),
+                _ => return Some((key(KeyCode::Esc), 1)),
+            };
+            Some((key(code), 3))
+        }
+        // `ESC` before another key means it was pressed with Alt.
+        Some(&b) if b < 0x80 && b != 0x1b => {
+            let mut event = ascii_key(b);
+            event.modifiers.insert(KeyModifiers::ALT);
+            Some((Some(Event::Key(event)), 2))
+        }
+        Some(_) => Some((key(KeyCode::Esc), 1)),
+    }
+}
+
+/// Returns the length of a UTF-8 sequence from its first byte, or `0` for
+/// a continuation or invalid byte.
+pub(crate) fn utf8_char_width(first_byte: u8) -> usize {
     if first_byte & 0b10000000 == 0 {
         1
     } else if first_byte & 0b11100000 == 0b11000000 {
@@ -445,6 +526,69 @@ mod tests {
             panic!("Expected Mouse event");
         }
     }
+
+    #[test]
+    fn test_parse_control_and_navigation_keys() {
+        let mut parser = Parser::new();
+        let key = |code| Event::Key(KeyEvent::new(code));
+        let with = |code, modifiers| Event::Key(KeyEvent::with_modifiers(code, modifiers));
+
+        let events = parser.parse(b"\x17\x7f\t\x1b[B\x1b[1;5C\x1b[3~\x1b[H\x1b[4~\x1bOP\x1b[Z");
+        assert_eq!(
+            events,
+            vec![
+                with(KeyCode::Char('w'), KeyModifiers::CTRL),
+                key(KeyCode::Backspace),
+                key(KeyCode::Tab),
+                key(KeyCode::Down),
+                with(KeyCode::Right, KeyModifiers::CTRL),
+                key(KeyCode::Delete),
+                key(KeyCode::Home),
+                key(KeyCode::End),
+                key(KeyCode::F(1)),
+                with(KeyCode::Tab, KeyModifiers::SHIFT),
+            ]
+        );
+
+        // Alt sends an escape before the key; unknown sequences are dropped.
+        let events = parser.parse(b"\x1bb\x1b[99xa");
+        assert_eq!(
+            events,
+            vec![
+                with(KeyCode::Char('b'), KeyModifiers::ALT),
+                key(KeyCode::Char('a'))
+            ]
+        );
+
+        // Incomplete sequences wait for more bytes.
+        assert!(parser.parse(b"\x1b[1;2").is_empty());
+        assert_eq!(
+            parser.parse(b"D"),
+            vec![with(KeyCode::Left, KeyModifiers::SHIFT)]
+        );
+    }
+
+    #[test]
+    fn test_parse_mouse_move() {
+        let mut parser = Parser::new();
+        // 35+32 = 67 ('C') -> motion with no button held
+        let events = parser.parse(b"\x1b[MC+&");
+
+        assert_eq!(
+            events,
+            vec![Event::Mouse(MouseEvent::new(10, 5, MouseKind::Moved))]
+        );
+
+        // 35+8 (Alt) +32 = 75 ('K'), then 0+4 (Shift) +32 = 36 ('$').
+        let events = parser.parse(b"\x1b[MK+&\x1b[M$+&");
+        assert_eq!(
+            events,
+            vec![
+                Event::Mouse(MouseEvent::new(10, 5, MouseKind::Moved)),
+                Event::Mouse(MouseEvent::new(10, 5, MouseKind::LeftClick)),
+            ]
+        );
+    }
 }
 
 #[cfg(test)]
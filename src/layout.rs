@@ -3,6 +3,8 @@
 //! The core type is [`Rect`], which represents a rectangular area on the screen.
 //! The [`Layout`] engine can split a [`Rect`] into multiple sub-rectangles based on [`Constraint`]s.
 
+use crate::widgets::Measure;
+
 /// The direction in which a rectangle is split.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -13,6 +15,10 @@ pub enum Direction {
 }
 
 /// Constraints used to define the size of a layout segment.
+///
+/// When the constraints ask for more space than available, segments shrink
+/// in order of priority (see [`Layout::solve`]): `Ratio` first, then
+/// `Percentage`, then `Length` and `Fit`, then `Min`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Constraint {
     /// Takes up the remaining available space.
@@ -30,6 +36,51 @@ pub enum Constraint {
     Min(u16),
     /// Takes up `Fill` space, but is at most `u16` cells.
     Max(u16),
+    /// Takes up as much space as the content needs.
+    ///
+    /// The size is measured by [`Layout::split_measured`], and shrinks like a
+    /// `Length`. Without a widget to measure, it takes no space.
+    Fit,
+}
+
+/// Spacing applied around the inside edges of a [`Rect`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Margin {
+    /// The number of columns removed from both the left and right edges.
+    pub horizontal: u16,
+    /// The number of rows removed from both the top and bottom edges.
+    pub vertical: u16,
+}
+
+impl Margin {
+    /// Creates a new margin.
+    pub fn new(horizontal: u16, vertical: u16) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// Creates a margin with the same value on every side.
+    pub fn uniform(value: u16) -> Self {
+        Self::new(value, value)
+    }
+}
+
+/// The width and height of an area, without a position.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Size {
+    /// The width in columns.
+    pub width: u16,
+    /// The height in rows.
+    pub height: u16,
+}
+
+impl Size {
+    /// Creates a new size.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
 }
 
 /// A rectangular area on the screen.
@@ -57,8 +108,21 @@ impl Rect {
     }
 
     /// Returns the total number of cells in the rectangle.
-    pub fn area(&self) -> u16 {
-        self.width * self.height
+    ///
+    /// The result is a `u32` because `width * height` easily exceeds `u16::MAX`
+    /// (a 300x300 virtual buffer already holds 90,000 cells).
+    pub fn area(&self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    /// Returns `true` if the rectangle has no width or no height.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns the width and height of the rectangle.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
     }
 
     /// Returns the x-coordinate of the left edge.
@@ -66,9 +130,11 @@ impl Rect {
         self.x
     }
 
-    /// Returns the x-coordinate of the right edge.
+    /// Returns the x-coordinate of the right edge (exclusive).
+    ///
+    /// Saturates at `u16::MAX` instead of overflowing.
     pub fn right(&self) -> u16 {
-        self.x + self.width
+        self.x.saturating_add(self.width)
     }
 
     /// Returns the y-coordinate of the top edge.
@@ -76,18 +142,358 @@ impl Rect {
         self.y
     }
 
-    /// Returns the y-coordinate of the bottom edge.
+    /// Returns the y-coordinate of the bottom edge (exclusive).
+    ///
+    /// Saturates at `u16::MAX` instead of overflowing.
     pub fn bottom(&self) -> u16 {
-        self.y + self.height
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns the right edge, or `None` if it doesn't fit in a `u16`.
+    pub fn checked_right(&self) -> Option<u16> {
+        self.x.checked_add(self.width)
+    }
+
+    /// Returns the bottom edge, or `None` if it doesn't fit in a `u16`.
+    pub fn checked_bottom(&self) -> Option<u16> {
+        self.y.checked_add(self.height)
+    }
+
+    /// Returns a copy of the rectangle with its size reduced so that the
+    /// right and bottom edges fit in a `u16`.
+    ///
+    /// After clamping, [`right`](Self::right) and [`bottom`](Self::bottom)
+    /// are exact rather than saturated.
+    pub fn clamped(&self) -> Rect {
+        Rect::new(
+            self.x,
+            self.y,
+            self.width.min(u16::MAX - self.x),
+            self.height.min(u16::MAX - self.y),
+        )
+    }
+
+    /// Returns `true` if the point `(x, y)` lies inside the rectangle.
+    ///
+    /// The right and bottom edges are exclusive.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Returns the area shared by both rectangles.
+    ///
+    /// If the rectangles don't overlap, the result is an empty rectangle
+    /// positioned at the clamped origin.
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+
+    /// Returns `true` if the rectangles share at least one cell.
+    pub fn intersects(&self, other: Rect) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Returns the rectangle shrunk by the given margin on every side.
+    ///
+    /// If the margin is larger than the rectangle, the result is empty.
+    pub fn inner(&self, margin: Margin) -> Rect {
+        let double_h = margin.horizontal.saturating_mul(2);
+        let double_v = margin.vertical.saturating_mul(2);
+        if self.width < double_h || self.height < double_v {
+            return Rect::new(
+                self.x.saturating_add(self.width / 2),
+                self.y.saturating_add(self.height / 2),
+                0,
+                0,
+            );
+        }
+        Rect::new(
+            self.x.saturating_add(margin.horizontal),
+            self.y.saturating_add(margin.vertical),
+            self.width - double_h,
+            self.height - double_v,
+        )
+    }
+
+    /// Returns the rectangle moved by `(dx, dy)`.
+    ///
+    /// The position saturates at `0` and `u16::MAX`, and the size is clamped
+    /// so that the rectangle still fits in `u16` coordinates.
+    pub fn offset(&self, dx: i32, dy: i32) -> Rect {
+        let x = (self.x as i64 + dx as i64).clamp(0, u16::MAX as i64) as u16;
+        let y = (self.y as i64 + dy as i64).clamp(0, u16::MAX as i64) as u16;
+        Rect::new(x, y, self.width, self.height).clamped()
+    }
+
+    /// Moves and shrinks the rectangle so it lies entirely within `bounds`.
+    ///
+    /// The rectangle is first shrunk to fit, then shifted back inside if it
+    /// sticks out of an edge. Useful for keeping popups on screen.
+    pub fn clamp_within(&self, bounds: Rect) -> Rect {
+        let bounds = bounds.clamped();
+        let width = self.width.min(bounds.width);
+        let height = self.height.min(bounds.height);
+        let x = self.x.clamp(bounds.x, bounds.right() - width);
+        let y = self.y.clamp(bounds.y, bounds.bottom() - height);
+        Rect::new(x, y, width, height)
+    }
+
+    /// Returns a `width` x `height` rectangle centered inside this one.
+    ///
+    /// The size is clamped to the size of this rectangle.
+    pub fn centered(&self, width: u16, height: u16) -> Rect {
+        let area = self.clamped();
+        let width = width.min(area.width);
+        let height = height.min(area.height);
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    /// Returns an iterator over every row of the rectangle, each as a
+    /// one-cell-high [`Rect`].
+    pub fn rows(&self) -> Rows {
+        let rect = self.clamped();
+        Rows {
+            rect,
+            front: 0,
+            back: rect.height,
+        }
+    }
+
+    /// Returns an iterator over every column of the rectangle, each as a
+    /// one-cell-wide [`Rect`].
+    pub fn columns(&self) -> Columns {
+        let rect = self.clamped();
+        Columns {
+            rect,
+            front: 0,
+            back: rect.width,
+        }
+    }
+
+    /// Returns an iterator over every `(x, y)` position in the rectangle,
+    /// in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (u16, u16)> {
+        let rect = *self;
+        rect.rows()
+            .flat_map(move |row| (row.x..row.right()).map(move |x| (x, row.y)))
+    }
+
+    /// Splits the rectangle into a left part of `width` columns and the
+    /// remaining right part.
+    ///
+    /// `width` is clamped to the width of the rectangle.
+    pub fn split_at_x(&self, width: u16) -> (Rect, Rect) {
+        let area = self.clamped();
+        let width = width.min(area.width);
+        (
+            Rect::new(area.x, area.y, width, area.height),
+            Rect::new(area.x + width, area.y, area.width - width, area.height),
+        )
+    }
+
+    /// Splits the rectangle into a top part of `height` rows and the
+    /// remaining bottom part.
+    ///
+    /// `height` is clamped to the height of the rectangle.
+    pub fn split_at_y(&self, height: u16) -> (Rect, Rect) {
+        let area = self.clamped();
+        let height = height.min(area.height);
+        (
+            Rect::new(area.x, area.y, area.width, height),
+            Rect::new(area.x, area.y + height, area.width, area.height - height),
+        )
+    }
+
+    /// Removes `n` rows from the top of the rectangle and returns them,
+    /// shrinking `self` to the remainder.
+    pub fn take_top(&mut self, n: u16) -> Rect {
+        let (top, rest) = self.split_at_y(n);
+        *self = rest;
+        top
+    }
+
+    /// Removes `n` rows from the bottom of the rectangle and returns them,
+    /// shrinking `self` to the remainder.
+    pub fn take_bottom(&mut self, n: u16) -> Rect {
+        let (rest, bottom) = self.split_at_y(self.height.saturating_sub(n));
+        *self = rest;
+        bottom
+    }
+
+    /// Removes `n` columns from the left of the rectangle and returns them,
+    /// shrinking `self` to the remainder.
+    pub fn take_left(&mut self, n: u16) -> Rect {
+        let (left, rest) = self.split_at_x(n);
+        *self = rest;
+        left
+    }
+
+    /// Removes `n` columns from the right of the rectangle and returns them,
+    /// shrinking `self` to the remainder.
+    pub fn take_right(&mut self, n: u16) -> Rect {
+        let (rest, right) = self.split_at_x(self.width.saturating_sub(n));
+        *self = rest;
+        right
+    }
+}
+
+/// An iterator over the rows of a [`Rect`], created by [`Rect::rows`].
+#[derive(Debug, Clone)]
+pub struct Rows {
+    rect: Rect,
+    front: u16,
+    back: u16,
+}
+
+impl Iterator for Rows {
+    type Item = Rect;
+
+    fn next(&mut self) -> Option<Rect> {
+        if self.front >= self.back {
+            return None;
+        }
+        let row = Rect::new(self.rect.x, self.rect.y + self.front, self.rect.width, 1);
+        self.front += 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.back - self.front) as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for Rows {
+    fn next_back(&mut self) -> Option<Rect> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Rect::new(
+            self.rect.x,
+            self.rect.y + self.back,
+            self.rect.width,
+            1,
+        ))
+    }
+}
+
+impl ExactSizeIterator for Rows {}
+
+/// An iterator over the columns of a [`Rect`], created by [`Rect::columns`].
+#[derive(Debug, Clone)]
+pub struct Columns {
+    rect: Rect,
+    front: u16,
+    back: u16,
+}
+
+impl Iterator for Columns {
+    type Item = Rect;
+
+    fn next(&mut self) -> Option<Rect> {
+        if self.front >= self.back {
+            return None;
+        }
+        let column = Rect::new(self.rect.x + self.front, self.rect.y, 1, self.rect.height);
+        self.front += 1;
+        Some(column)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.back - self.front) as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for Columns {
+    fn next_back(&mut self) -> Option<Rect> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Rect::new(
+            self.rect.x + self.back,
+            self.rect.y,
+            1,
+            self.rect.height,
+        ))
     }
 }
 
+impl ExactSizeIterator for Columns {}
+
+/// How a [`Layout`] uses the space left over when its constraints don't fill
+/// the area.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flex {
+    /// Segments are packed at the start; the free space is at the end.
+    #[default]
+    Start,
+    /// Segments are packed at the end; the free space is at the start.
+    End,
+    /// Segments are packed in the middle.
+    Center,
+    /// The free space is shared between the gaps; the first and last
+    /// segments touch the edges.
+    SpaceBetween,
+    /// The free space is shared around every segment, so the edges get half
+    /// as much as the gaps between segments.
+    SpaceAround,
+    /// The free space is shared between the segments, which grow to fill
+    /// the area.
+    Stretch,
+}
+
 /// A layout engine that divides a rectangle into sub-rectangles based on constraints.
+///
+/// # Example
+/// ```
+/// use phosphor::{Constraint, Direction, Flex, Layout, Rect};
+///
+/// let [left, right] = Layout::new(
+///     Direction::Horizontal,
+///     vec![Constraint::Length(10), Constraint::Length(10)],
+/// )
+/// .margin(1)
+/// .spacing(2)
+/// .flex(Flex::Center)
+/// .split_to(Rect::new(0, 0, 40, 10));
+///
+/// assert_eq!(left, Rect::new(9, 1, 10, 8));
+/// assert_eq!(right, Rect::new(21, 1, 10, 8));
+/// ```
 pub struct Layout {
     /// The direction of the split.
     pub direction: Direction,
     /// The constraints for each segment.
     pub constraints: Vec<Constraint>,
+    /// The number of cells between two segments.
+    pub spacing: u16,
+    /// The space removed around the area before splitting it.
+    pub margin: Margin,
+    /// How leftover space is distributed.
+    pub flex: Flex,
+    /// Segments hidden when the area is too small, as `(index, min_size)`.
+    pub optional: Vec<(usize, u16)>,
 }
 
 impl Layout {
@@ -96,64 +502,214 @@ impl Layout {
         Self {
             direction,
             constraints,
+            spacing: 0,
+            margin: Margin::default(),
+            flex: Flex::default(),
+            optional: Vec::new(),
         }
     }
 
+    /// Sets the number of cells between two segments.
+    ///
+    /// Spacing is reduced when the area is too small to fit every gap.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the same margin on every side of the area.
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = Margin::uniform(margin);
+        self
+    }
+
+    /// Sets the margin on the left and right sides of the area.
+    pub fn horizontal_margin(mut self, margin: u16) -> Self {
+        self.margin.horizontal = margin;
+        self
+    }
+
+    /// Sets the margin on the top and bottom sides of the area.
+    pub fn vertical_margin(mut self, margin: u16) -> Self {
+        self.margin.vertical = margin;
+        self
+    }
+
+    /// Sets how leftover space is distributed.
+    pub fn flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    /// Hides the segment at `index` when the area, margin removed, is less
+    /// than `min_size` cells along the layout direction.
+    ///
+    /// A hidden segment gets an empty rectangle and no spacing, and the other
+    /// segments share its space. The number of rectangles doesn't change, so
+    /// [`split_to`](Self::split_to) keeps working.
+    pub fn optional(mut self, index: usize, min_size: u16) -> Self {
+        self.optional.push((index, min_size));
+        self
+    }
+
     /// Splits the given rectangle into sub-rectangles.
     ///
     /// The number of returned rectangles matches the number of constraints.
+    /// The margin is removed first, then the sizes are resolved by
+    /// [`solve`](Self::solve) for the space left between the gaps. Any space
+    /// the constraints leave free is distributed according to [`Flex`].
+    ///
+    /// [`Constraint::Fit`] segments take no space; use
+    /// [`split_measured`](Self::split_measured) to size them to their content.
     pub fn split(&self, rect: Rect) -> Vec<Rect> {
-        let mut rects = Vec::new();
-        let total_space = match &self.direction {
-            Direction::Horizontal => rect.width,
-            Direction::Vertical => rect.height,
-        };
+        self.split_constraints(rect, &self.constraints)
+    }
 
-        let start_x = rect.x;
-        let start_y = rect.y;
-        let mut offset = 0;
+    /// Splits the given rectangle like [`split`](Self::split), sizing every
+    /// [`Constraint::Fit`] segment to the widget at the same index.
+    ///
+    /// Widgets are measured with the width of the area (margin removed): a
+    /// horizontal layout uses their preferred width, a vertical layout their
+    /// preferred height. Entries for other constraints are ignored, and
+    /// `Fit` segments without a widget take no space.
+    ///
+    /// # Example
+    /// ```
+    /// use phosphor::{Constraint, Direction, Layout, Rect, widgets::Text};
+    ///
+    /// let label = Text::new("Name:");
+    /// let [left, right] = Layout::new(
+    ///     Direction::Horizontal,
+    ///     vec![Constraint::Fit, Constraint::Fill],
+    /// )
+    /// .split_measured(Rect::new(0, 0, 20, 1), &[&label])
+    /// .try_into()
+    /// .unwrap();
+    ///
+    /// assert_eq!(left, Rect::new(0, 0, 5, 1));
+    /// assert_eq!(right, Rect::new(5, 0, 15, 1));
+    /// ```
+    pub fn split_measured(&self, rect: Rect, widgets: &[&dyn Measure]) -> Vec<Rect> {
+        let width = rect.inner(self.margin).width;
+        let constraints: Vec<Constraint> = self
+            .constraints
+            .iter()
+            .enumerate()
+            .map(|(i, &constraint)| match (constraint, widgets.get(i)) {
+                (Constraint::Fit, Some(widget)) => {
+                    let size = widget.preferred_size(width);
+                    Constraint::Length(match self.direction {
+                        Direction::Horizontal => size.width,
+                        Direction::Vertical => size.height,
+                    })
+                }
+                (constraint, _) => constraint,
+            })
+            .collect();
 
-        // 1. Calculate used space and count fills
-        let mut used_space = 0;
-        let mut flex_count = 0;
+        self.split_constraints(rect, &constraints)
+    }
 
-        for c in &self.constraints {
-            match c {
-                Constraint::Length(l) => used_space += l,
-                Constraint::Percentage(p) => used_space += (p * total_space) / 100,
-                Constraint::Ratio(n, d) => used_space += (total_space as u32 * n / d) as u16,
-                Constraint::Fill | Constraint::Min(_) | Constraint::Max(_) => flex_count += 1,
-            }
+    /// Helper to split `rect` with `constraints` in place of the layout's own,
+    /// leaving out the hidden optional segments.
+    fn split_constraints(&self, rect: Rect, constraints: &[Constraint]) -> Vec<Rect> {
+        let area = rect.inner(self.margin);
+        let total = match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        };
+        let hidden = |i: usize| {
+            self.optional
+                .iter()
+                .any(|&(index, min_size)| index == i && total < min_size)
+        };
+        if !(0..constraints.len()).any(hidden) {
+            return self.split_visible(area, constraints);
         }
 
-        // 2. Calculate size of one `Fill` unit
-        let flex_size = if flex_count > 0 {
-            total_space.saturating_sub(used_space) / flex_count
-        } else {
-            0
+        let visible: Vec<Constraint> = (0..)
+            .zip(constraints)
+            .filter(|&(i, _)| !hidden(i))
+            .map(|(_, c)| *c)
+            .collect();
+        let mut rects = self.split_visible(area, &visible).into_iter();
+
+        // Hidden segments are empty and sit where the previous segment ends.
+        let mut end = match self.direction {
+            Direction::Horizontal => Rect::new(area.x, area.y, 0, area.height),
+            Direction::Vertical => Rect::new(area.x, area.y, area.width, 0),
         };
+        (0..constraints.len())
+            .map(|i| {
+                if hidden(i) {
+                    return end;
+                }
+                let rect = rects.next().unwrap_or(end);
+                end = match self.direction {
+                    Direction::Horizontal => Rect::new(rect.right(), rect.y, 0, rect.height),
+                    Direction::Vertical => Rect::new(rect.x, rect.bottom(), rect.width, 0),
+                };
+                rect
+            })
+            .collect()
+    }
 
-        // 3. Create rects
-        for c in &self.constraints {
-            let size = match c {
-                Constraint::Length(l) => *l,
-                Constraint::Percentage(p) => (p * total_space) / 100,
-                Constraint::Fill => flex_size,
-                Constraint::Ratio(n, d) => (total_space as u32 * n / d) as u16,
-                Constraint::Min(n) => flex_size.max(*n),
-                Constraint::Max(n) => flex_size.min(*n),
-            };
+    /// Helper to split an area, margin already removed, with `constraints`.
+    fn split_visible(&self, area: Rect, constraints: &[Constraint]) -> Vec<Rect> {
+        let total = match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        } as u32;
 
-            let sub_rect = match &self.direction {
-                Direction::Horizontal => Rect::new(start_x + offset, start_y, size, rect.height),
-                Direction::Vertical => Rect::new(start_x, start_y + offset, rect.width, size),
-            };
+        let count = constraints.len() as u32;
+        let gaps = count.saturating_sub(1);
+        let spacing = (self.spacing as u32).min(total.checked_div(gaps).unwrap_or(0));
+        let available = total - spacing * gaps;
+
+        let mut sizes: Vec<u32> = solve(constraints, available as u16)
+            .into_iter()
+            .map(u32::from)
+            .collect();
+        let leftover = available - sizes.iter().sum::<u32>();
+
+        // Share `amount` over `parts` slots so that slot `i` starts at
+        // `share(i)`, without losing the remainder.
+        let share = |amount: u32, i: u32, parts: u32| {
+            ((amount as u64 * i as u64).checked_div(parts as u64)).unwrap_or(0) as u32
+        };
 
-            rects.push(sub_rect);
-            offset += size;
+        if self.flex == Flex::Stretch {
+            for (i, size) in (0..).zip(sizes.iter_mut()) {
+                *size += share(leftover, i + 1, count) - share(leftover, i, count);
+            }
         }
 
-        rects
+        let mut offset: u32 = 0;
+        (0..)
+            .zip(sizes)
+            .map(|(i, size)| {
+                let extra = match self.flex {
+                    Flex::Start | Flex::Stretch => 0,
+                    Flex::End => leftover,
+                    Flex::Center => leftover / 2,
+                    Flex::SpaceBetween => share(leftover, i, gaps),
+                    Flex::SpaceAround => share(leftover, 2 * i + 1, 2 * count),
+                };
+                let start = (offset + extra).min(u16::MAX as u32) as u16;
+                offset += size + spacing;
+
+                let size = size as u16;
+                let sub_rect = match self.direction {
+                    Direction::Horizontal => {
+                        Rect::new(area.x.saturating_add(start), area.y, size, area.height)
+                    }
+                    Direction::Vertical => {
+                        Rect::new(area.x, area.y.saturating_add(start), area.width, size)
+                    }
+                };
+                sub_rect.clamped()
+            })
+            .collect()
     }
 
     /// Splits the given rectangle into a fixed-size array of sub-rectangles.
@@ -164,6 +720,285 @@ impl Layout {
         let rects = self.split(rect);
         rects.try_into().expect("Layout constraints count mismatch")
     }
+
+    /// Resolves the size of every segment for a total of `total` cells.
+    ///
+    /// 1. `Length`, `Percentage` and `Ratio` take their requested size, and
+    ///    `Min` reserves its minimum. `Fit` has nothing to measure here and
+    ///    takes no space.
+    /// 2. If that exceeds `total`, segments are shrunk by priority, lowest
+    ///    first: `Ratio`, then `Percentage`, then `Length`, then the minimum
+    ///    of `Min`. Flexible segments get nothing.
+    /// 3. Otherwise, the remaining space is shared equally between `Fill`,
+    ///    `Min` and `Max` segments, clamped to their bounds.
+    /// 4. Fractional sizes are rounded so that rounding errors never
+    ///    accumulate: the segments cover exactly `total` cells whenever the
+    ///    constraints ask for all of the space (a `Fill` or `Min` segment, or
+    ///    overflowing fixed sizes).
+    ///
+    /// The sizes never add up to more than `total`.
+    pub fn solve(&self, total: u16) -> Vec<u16> {
+        solve(&self.constraints, total)
+    }
+}
+
+/// Helper behind [`Layout::solve`], for any list of constraints.
+fn solve(constraints: &[Constraint], total: u16) -> Vec<u16> {
+    let total_f = total as f64;
+    let mut segments: Vec<Segment> = constraints
+        .iter()
+        .map(|c| Segment::new(*c, total_f))
+        .collect();
+
+    let fixed: f64 = segments.iter().filter(|s| !s.flex).map(|s| s.size).sum();
+    let reserved: f64 = segments.iter().filter(|s| s.flex).map(|s| s.min).sum();
+
+    if fixed + reserved > total_f {
+        shrink(&mut segments, fixed + reserved - total_f);
+    } else {
+        share(&mut segments, total_f - fixed);
+    }
+
+    round_sizes(&segments, total)
+}
+
+/// The priority of a constraint when space runs out. Lower values shrink first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Priority {
+    Flex,
+    Ratio,
+    Percentage,
+    Length,
+    Min,
+}
+
+/// A segment being solved, with its size in fractional cells.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    size: f64,
+    min: f64,
+    max: f64,
+    flex: bool,
+    priority: Priority,
+}
+
+impl Segment {
+    fn new(constraint: Constraint, total: f64) -> Self {
+        let fixed = |size: f64, priority| Segment {
+            size,
+            min: size,
+            max: size,
+            flex: false,
+            priority,
+        };
+        let flex = |min: f64, max: f64, priority| Segment {
+            size: min,
+            min,
+            max,
+            flex: true,
+            priority,
+        };
+
+        match constraint {
+            Constraint::Length(l) => fixed(l as f64, Priority::Length),
+            Constraint::Fit => fixed(0.0, Priority::Length),
+            Constraint::Percentage(p) => fixed(total * p as f64 / 100.0, Priority::Percentage),
+            Constraint::Ratio(_, 0) => fixed(0.0, Priority::Ratio),
+            Constraint::Ratio(n, d) => fixed(total * n as f64 / d as f64, Priority::Ratio),
+            Constraint::Min(n) => flex(n as f64, f64::INFINITY, Priority::Min),
+            Constraint::Max(n) => flex(0.0, n as f64, Priority::Flex),
+            Constraint::Fill => flex(0.0, f64::INFINITY, Priority::Flex),
+        }
+    }
+
+    /// Returns `true` if the segment grows to take any free space.
+    fn is_unbounded(&self) -> bool {
+        self.flex && self.max.is_infinite()
+    }
+}
+
+/// Helper to remove `overflow` cells, shrinking the lowest priorities first.
+///
+/// Within a priority, segments shrink in proportion to their size.
+fn shrink(segments: &mut [Segment], mut overflow: f64) {
+    for segment in segments.iter_mut().filter(|s| s.flex) {
+        segment.size = segment.min;
+    }
+
+    let priorities = [
+        Priority::Ratio,
+        Priority::Percentage,
+        Priority::Length,
+        Priority::Min,
+    ];
+    for priority in priorities {
+        if overflow <= 0.0 {
+            break;
+        }
+        let group = segments.iter().filter(|s| s.priority == priority);
+        let group_total: f64 = group.map(|s| s.size).sum();
+        if group_total <= 0.0 {
+            continue;
+        }
+
+        let scale = ((group_total - overflow) / group_total).max(0.0);
+        for segment in segments.iter_mut().filter(|s| s.priority == priority) {
+            segment.size *= scale;
+        }
+        overflow -= group_total;
+    }
+}
+
+/// Helper to share `free` cells between the flexible segments.
+///
+/// Every flexible segment gets the same share, clamped to its bounds. The
+/// share is found by bisection, since the clamped sum grows with it.
+fn share(segments: &mut [Segment], free: f64) {
+    let sum = |segments: &[Segment], s: f64| -> f64 {
+        segments
+            .iter()
+            .filter(|seg| seg.flex)
+            .map(|seg| s.clamp(seg.min, seg.max))
+            .sum()
+    };
+
+    let mut low = 0.0;
+    let mut high = free;
+    for _ in 0..64 {
+        let mid = (low + high) / 2.0;
+        if sum(segments, mid) < free {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    for segment in segments.iter_mut().filter(|s| s.flex) {
+        segment.size = high.clamp(segment.min, segment.max);
+    }
+}
+
+/// Helper to round fractional sizes to whole cells.
+///
+/// Each segment ends at the rounded-down running total, so integer sizes
+/// stay exact and the fractional parts carry over to the next segment
+/// instead of being lost.
+fn round_sizes(segments: &[Segment], total: u16) -> Vec<u16> {
+    // Tolerance for floating point error in the running total.
+    const EPSILON: f64 = 1e-6;
+
+    let fills = segments.iter().any(Segment::is_unbounded);
+    let mut end = 0.0;
+    let mut previous: u16 = 0;
+    let mut sizes = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        end += segment.size;
+        let boundary = (end + EPSILON).floor().clamp(0.0, total as f64) as u16;
+        let boundary = boundary.max(previous);
+        sizes.push(boundary - previous);
+        previous = boundary;
+    }
+
+    // Give cells lost to floating point error to the last growing segment.
+    let reaches_total = fills || end + EPSILON >= total as f64;
+    if reaches_total && previous < total {
+        let index = segments
+            .iter()
+            .rposition(Segment::is_unbounded)
+            .or_else(|| sizes.iter().rposition(|&s| s > 0))
+            .unwrap_or(sizes.len().saturating_sub(1));
+        if let Some(size) = sizes.get_mut(index) {
+            *size += total - previous;
+        }
+    }
+
+    sizes
+}
+
+/// Splits an area with nested layouts and binds every segment to a name.
+///
+/// The macro expands to `let` statements, so the names are available after
+/// it. A layout is `vertical[...]` or `horizontal[...]` with a list of
+/// `constraint: target` items, where the target is a name, `_` to skip the
+/// segment, or a nested layout. The constraints are:
+///
+/// * `len(n)`: [`Constraint::Length`]
+/// * `pct(n)`: [`Constraint::Percentage`]
+/// * `ratio(a, b)`: [`Constraint::Ratio`]
+/// * `min(n)`: [`Constraint::Min`]
+/// * `max(n)`: [`Constraint::Max`]
+/// * `fill`: [`Constraint::Fill`]
+///
+/// Every layout is split with [`Layout::split_to`], whose array size comes
+/// from the same items as the constraints, so the number of names always
+/// matches at compile time.
+///
+/// # Example
+/// ```
+/// use phosphor::{Rect, layout};
+///
+/// let area = Rect::new(0, 0, 100, 30);
+/// layout!(area => vertical[
+///     len(3): header,
+///     fill: horizontal[pct(30): nav, fill: body],
+///     len(1): status,
+/// ]);
+///
+/// assert_eq!(header, Rect::new(0, 0, 100, 3));
+/// assert_eq!(nav, Rect::new(0, 3, 30, 26));
+/// assert_eq!(body, Rect::new(30, 3, 70, 26));
+/// assert_eq!(status, Rect::new(0, 29, 100, 1));
+/// ```
+#[macro_export]
+macro_rules! layout {
+    ($area:expr => $direction:ident [$($items:tt)*]) => {
+        $crate::layout!(@items $direction ($area) [] [] [] $($items)*);
+    };
+
+    // A nested layout, split from a temporary bound in this expansion.
+    (@items $direction:tt $area:tt [$($c:tt)*] [$($p:tt)*] [$($n:tt)*]
+        $kind:ident $(($($arg:expr),*))? : $child:ident [$($inner:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::layout!(@items $direction $area
+            [$($c)* $crate::layout!(@constraint $kind $(($($arg),*))?),]
+            [$($p)* area,]
+            [$($n)* $crate::layout!(area => $child [$($inner)*]);]
+            $($($rest)*)?)
+    };
+    (@items $direction:tt $area:tt [$($c:tt)*] [$($p:tt)*] [$($n:tt)*]
+        $kind:ident $(($($arg:expr),*))? : _ $(, $($rest:tt)*)?) => {
+        $crate::layout!(@items $direction $area
+            [$($c)* $crate::layout!(@constraint $kind $(($($arg),*))?),]
+            [$($p)* _,]
+            [$($n)*]
+            $($($rest)*)?)
+    };
+    (@items $direction:tt $area:tt [$($c:tt)*] [$($p:tt)*] [$($n:tt)*]
+        $kind:ident $(($($arg:expr),*))? : $name:ident $(, $($rest:tt)*)?) => {
+        $crate::layout!(@items $direction $area
+            [$($c)* $crate::layout!(@constraint $kind $(($($arg),*))?),]
+            [$($p)* $name,]
+            [$($n)*]
+            $($($rest)*)?)
+    };
+    (@items $direction:tt $area:tt [$($c:tt)*] [$($p:tt)*] [$($n:tt)*]) => {
+        let [$($p)*] = $crate::Layout::new(
+            $crate::layout!(@direction $direction),
+            vec![$($c)*],
+        )
+        .split_to($area);
+        $($n)*
+    };
+
+    (@direction vertical) => { $crate::Direction::Vertical };
+    (@direction horizontal) => { $crate::Direction::Horizontal };
+
+    (@constraint len($n:expr)) => { $crate::Constraint::Length($n) };
+    (@constraint pct($n:expr)) => { $crate::Constraint::Percentage($n) };
+    (@constraint ratio($a:expr, $b:expr)) => { $crate::Constraint::Ratio($a, $b) };
+    (@constraint min($n:expr)) => { $crate::Constraint::Min($n) };
+    (@constraint max($n:expr)) => { $crate::Constraint::Max($n) };
+    (@constraint fill) => { $crate::Constraint::Fill };
 }
 
 #[cfg(test)]
@@ -265,4 +1100,460 @@ mod tests {
         let rects_max = layout_max.split(rect);
         assert_eq!(rects_max[1].height, 40);
     }
+
+    #[test]
+    fn test_rect_extreme_sizes() {
+        let rect = Rect::new(0, 0, u16::MAX, u16::MAX);
+        assert_eq!(rect.area(), u16::MAX as u32 * u16::MAX as u32);
+
+        let edge = Rect::new(u16::MAX - 5, u16::MAX - 2, 100, 100);
+        assert_eq!(edge.right(), u16::MAX);
+        assert_eq!(edge.bottom(), u16::MAX);
+        assert_eq!(edge.checked_right(), None);
+        assert_eq!(edge.checked_bottom(), None);
+        assert_eq!(edge.clamped(), Rect::new(u16::MAX - 5, u16::MAX - 2, 5, 2));
+
+        let fits = Rect::new(10, 10, 5, 5);
+        assert_eq!(fits.checked_right(), Some(15));
+        assert_eq!(fits.clamped(), fits);
+        assert!(Rect::new(3, 3, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_layout_split_large_area() {
+        // `p * total_space` would overflow a u16 here.
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Percentage(50), Constraint::Fill],
+        );
+        let rects = layout.split(Rect::new(0, 0, u16::MAX, 1));
+
+        assert_eq!(rects[0].width, 32767);
+        assert_eq!(rects[1].x, 32767);
+        assert_eq!(rects[1].width, 32768);
+    }
+
+    #[test]
+    fn test_layout_split_overflowing_lengths() {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![
+                Constraint::Length(u16::MAX),
+                Constraint::Length(u16::MAX),
+                Constraint::Ratio(1, 0),
+            ],
+        );
+        let rects = layout.split(Rect::new(0, 10, 10, 10));
+
+        // Overflowing lengths shrink to share the parent instead of overlapping.
+        assert_eq!(rects[0], Rect::new(0, 10, 10, 5));
+        assert_eq!(rects[1], Rect::new(0, 15, 10, 5));
+        assert_eq!(rects[2], Rect::new(0, 20, 10, 0));
+    }
+
+    #[test]
+    fn test_layout_spacing_and_margin() {
+        let layout = Layout::new(Direction::Horizontal, vec![Constraint::Fill; 3])
+            .spacing(1)
+            .horizontal_margin(2)
+            .vertical_margin(1);
+        let rects = layout.split(Rect::new(0, 0, 15, 5));
+
+        assert_eq!(rects[0], Rect::new(2, 1, 3, 3));
+        assert_eq!(rects[1], Rect::new(6, 1, 3, 3));
+        assert_eq!(rects[2], Rect::new(10, 1, 3, 3));
+
+        // Spacing shrinks when the gaps don't fit.
+        let tight = Layout::new(Direction::Horizontal, vec![Constraint::Fill; 3]).spacing(10);
+        let rects = tight.split(Rect::new(0, 0, 4, 1));
+        assert_eq!(rects.iter().map(|r| r.x).collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert!(rects.iter().all(|r| r.width == 0));
+    }
+
+    #[test]
+    fn test_layout_flex_modes() {
+        let xs = |flex: Flex| {
+            Layout::new(Direction::Horizontal, vec![Constraint::Length(2); 3])
+                .flex(flex)
+                .split(Rect::new(0, 0, 12, 1))
+                .iter()
+                .map(|r| (r.x, r.width))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(xs(Flex::Start), vec![(0, 2), (2, 2), (4, 2)]);
+        assert_eq!(xs(Flex::End), vec![(6, 2), (8, 2), (10, 2)]);
+        assert_eq!(xs(Flex::Center), vec![(3, 2), (5, 2), (7, 2)]);
+        assert_eq!(xs(Flex::SpaceBetween), vec![(0, 2), (5, 2), (10, 2)]);
+        assert_eq!(xs(Flex::SpaceAround), vec![(1, 2), (5, 2), (9, 2)]);
+        assert_eq!(xs(Flex::Stretch), vec![(0, 4), (4, 4), (8, 4)]);
+
+        // Flex has no effect when the constraints fill the area.
+        let full = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Length(3), Constraint::Fill],
+        )
+        .flex(Flex::End)
+        .split(Rect::new(0, 0, 1, 10));
+        assert_eq!(full[0], Rect::new(0, 0, 1, 3));
+    }
+
+    #[test]
+    fn test_layout_distributes_remainder() {
+        let fills = Layout::new(Direction::Horizontal, vec![Constraint::Fill; 3]);
+        assert_eq!(fills.solve(10), vec![3, 3, 4]);
+
+        let thirds = Layout::new(Direction::Horizontal, vec![Constraint::Ratio(1, 3); 3]);
+        assert_eq!(thirds.solve(10), vec![3, 3, 4]);
+
+        let halves = Layout::new(Direction::Horizontal, vec![Constraint::Percentage(50); 2]);
+        assert_eq!(halves.solve(101), vec![50, 51]);
+    }
+
+    #[test]
+    fn test_layout_overflow_shrinks_by_priority() {
+        // Percentages give way before lengths.
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Percentage(60), Constraint::Length(60)],
+        );
+        assert_eq!(layout.solve(100), vec![40, 60]);
+
+        // Minimums are kept the longest; fills get nothing.
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![
+                Constraint::Fill,
+                Constraint::Length(8),
+                Constraint::Min(6),
+                Constraint::Max(4),
+            ],
+        );
+        assert_eq!(layout.solve(10), vec![0, 4, 6, 0]);
+        assert_eq!(layout.solve(4), vec![0, 0, 4, 0]);
+    }
+
+    #[test]
+    fn test_layout_min_max_clamping() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Max(10), Constraint::Min(50), Constraint::Fill],
+        );
+        // An equal share of 100 is 33: Max caps at 10, Min lifts to 50.
+        assert_eq!(layout.solve(100), vec![10, 50, 40]);
+
+        // Max-only layouts leave the rest of the space free.
+        let capped = Layout::new(Direction::Horizontal, vec![Constraint::Max(3); 2]);
+        assert_eq!(capped.solve(10), vec![3, 3]);
+    }
+
+    #[test]
+    fn test_layout_macro() {
+        let area = Rect::new(0, 0, 40, 12);
+        let offset = 2;
+        layout!(area => horizontal[
+            ratio(1, 4): left,
+            len(offset): _,
+            fill: vertical[
+                min(2): top,
+                len(3): horizontal[max(5): a, fill: b],
+                pct(25): horizontal[fill: c],
+            ],
+        ]);
+
+        assert_eq!(left, Rect::new(0, 0, 10, 12));
+        assert_eq!(top, Rect::new(12, 0, 28, 6));
+        assert_eq!(a, Rect::new(12, 6, 5, 3));
+        assert_eq!(b, Rect::new(17, 6, 23, 3));
+        assert_eq!(c, Rect::new(12, 9, 28, 3));
+    }
+
+    #[test]
+    fn test_layout_optional_segments() {
+        let layout = Layout::new(Direction::Vertical, vec![Constraint::Length(3); 3])
+            .spacing(1)
+            .optional(1, 11);
+
+        assert_eq!(
+            layout.split(Rect::new(0, 0, 4, 10)),
+            vec![
+                Rect::new(0, 0, 4, 3),
+                Rect::new(0, 3, 4, 0),
+                Rect::new(0, 4, 4, 3),
+            ]
+        );
+        assert_eq!(
+            layout.split(Rect::new(0, 0, 4, 11))[1],
+            Rect::new(0, 4, 4, 3)
+        );
+    }
+
+    #[test]
+    fn test_layout_split_measured_fits_content() {
+        use crate::widgets::{Block, Borders, Text};
+
+        let title = Text::new("Title");
+        let body = Text::new("one two three four").wrap(true);
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Fit, Constraint::Fit, Constraint::Fill],
+        )
+        .margin(1);
+        let rects = layout.split_measured(Rect::new(0, 0, 10, 10), &[&title, &body]);
+        assert_eq!(rects[0], Rect::new(1, 1, 8, 1));
+        assert_eq!(rects[1], Rect::new(1, 2, 8, 3));
+        assert_eq!(rects[2], Rect::new(1, 5, 8, 4));
+
+        // Without widgets, `Fit` takes no space.
+        assert_eq!(layout.split(Rect::new(0, 0, 10, 10))[0].height, 0);
+
+        // Fitted segments shrink like lengths when space runs out.
+        let block = Block::new().borders(Borders::ALL).title("Long title");
+        let row = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Fit, Constraint::Length(10)],
+        );
+        let sizes: Vec<u16> = row
+            .split_measured(Rect::new(0, 0, 30, 1), &[&block])
+            .iter()
+            .map(|r| r.width)
+            .collect();
+        assert_eq!(sizes, vec![16, 10]);
+        let sizes: Vec<u16> = row
+            .split_measured(Rect::new(0, 0, 13, 1), &[&block])
+            .iter()
+            .map(|r| r.width)
+            .collect();
+        assert_eq!(sizes, vec![7, 6]);
+    }
+
+    /// A small xorshift generator, so property tests are reproducible
+    /// without external crates.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn constraint(&mut self) -> Constraint {
+            let n = self.below(120) as u16;
+            match self.below(7) {
+                0 => Constraint::Fill,
+                1 => Constraint::Percentage(n),
+                2 => Constraint::Length(n),
+                3 => Constraint::Ratio(self.below(5) as u32, self.below(5) as u32),
+                4 => Constraint::Min(n),
+                5 => Constraint::Max(n),
+                _ => Constraint::Fit,
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout_solver_properties() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..5000 {
+            let count = 1 + rng.below(6) as usize;
+            let constraints: Vec<Constraint> = (0..count).map(|_| rng.constraint()).collect();
+            let total = match rng.below(4) {
+                0 => rng.below(5) as u16,
+                1 => u16::MAX - rng.below(5) as u16,
+                _ => rng.below(300) as u16,
+            };
+
+            let layout = Layout::new(Direction::Horizontal, constraints.clone());
+            let sizes = layout.solve(total);
+            let sum: u32 = sizes.iter().map(|&s| s as u32).sum();
+            let context = format!("{:?} in {} -> {:?}", constraints, total, sizes);
+
+            // Never more than the parent.
+            assert!(sum <= total as u32, "{}", context);
+
+            // Exactly the parent when something wants all of the space.
+            let requested: f64 = constraints
+                .iter()
+                .map(|c| Segment::new(*c, total as f64))
+                .map(|s| if s.flex { s.min } else { s.size })
+                .sum();
+            let greedy = constraints
+                .iter()
+                .any(|c| matches!(c, Constraint::Fill | Constraint::Min(_)));
+            if greedy || requested >= total as f64 {
+                assert_eq!(sum, total as u32, "{}", context);
+            }
+
+            // Bounds hold whenever there is room for every constraint.
+            for (c, &size) in constraints.iter().zip(&sizes) {
+                match *c {
+                    Constraint::Max(n) => assert!(size <= n, "{}", context),
+                    Constraint::Min(n) if requested <= total as f64 => {
+                        assert!(size >= n, "{}", context)
+                    }
+                    Constraint::Length(n) if requested <= total as f64 => {
+                        assert_eq!(size, n, "{}", context)
+                    }
+                    _ => {}
+                }
+            }
+
+            // Rects are contiguous and stay inside the parent.
+            let parent = Rect::new(3, 0, total, 1).clamped();
+            let rects = layout.split(parent);
+            let mut x = parent.x;
+            for rect in &rects {
+                assert_eq!(rect.x, x, "{}", context);
+                assert_eq!(parent.union(*rect), parent, "{}", context);
+                x = x.saturating_add(rect.width);
+            }
+
+            // With any flex mode and spacing, rects stay ordered, apart and inside.
+            let flex = [
+                Flex::Start,
+                Flex::End,
+                Flex::Center,
+                Flex::SpaceBetween,
+                Flex::SpaceAround,
+                Flex::Stretch,
+            ][rng.below(6) as usize];
+            let spacing = rng.below(4) as u16;
+            let layout = Layout::new(Direction::Horizontal, constraints.clone())
+                .flex(flex)
+                .spacing(spacing);
+            let rects = layout.split(parent);
+            for pair in rects.windows(2) {
+                assert!(pair[0].right() <= pair[1].x, "{:?} {}", flex, context);
+            }
+            for rect in &rects {
+                assert_eq!(parent.union(*rect), parent, "{:?} {}", flex, context);
+            }
+            if flex == Flex::Stretch {
+                let last = rects.last().unwrap();
+                assert_eq!(last.right(), parent.right(), "{}", context);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let rect = Rect::new(2, 3, 4, 5);
+        assert!(rect.contains(2, 3));
+        assert!(rect.contains(5, 7));
+        assert!(!rect.contains(6, 3)); // right edge is exclusive
+        assert!(!rect.contains(2, 8)); // bottom edge is exclusive
+        assert!(!rect.contains(1, 3));
+        assert!(!Rect::new(0, 0, 0, 0).contains(0, 0));
+    }
+
+    #[test]
+    fn test_rect_intersection_and_union() {
+        let a = Rect::new(0, 0, 10, 10);
+        let b = Rect::new(5, 5, 10, 10);
+        assert_eq!(a.intersection(b), Rect::new(5, 5, 5, 5));
+        assert_eq!(a.union(b), Rect::new(0, 0, 15, 15));
+        assert!(a.intersects(b));
+
+        let far = Rect::new(20, 20, 2, 2);
+        assert!(a.intersection(far).is_empty());
+        assert!(!a.intersects(far));
+
+        let edge = Rect::new(u16::MAX - 1, 0, 10, 1);
+        assert_eq!(edge.union(a), Rect::new(0, 0, u16::MAX, 10));
+    }
+
+    #[test]
+    fn test_rect_inner_margin() {
+        let rect = Rect::new(10, 10, 10, 6);
+        assert_eq!(rect.inner(Margin::new(2, 1)), Rect::new(12, 11, 6, 4));
+        assert_eq!(rect.inner(Margin::uniform(0)), rect);
+        assert!(rect.inner(Margin::uniform(u16::MAX)).is_empty());
+    }
+
+    #[test]
+    fn test_rect_offset() {
+        let rect = Rect::new(5, 5, 3, 3);
+        assert_eq!(rect.offset(2, -3), Rect::new(7, 2, 3, 3));
+        assert_eq!(rect.offset(-10, -10), Rect::new(0, 0, 3, 3));
+        assert_eq!(rect.offset(i32::MAX, 0), Rect::new(u16::MAX, 5, 0, 3));
+    }
+
+    #[test]
+    fn test_rect_clamp_within() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let popup = Rect::new(70, 20, 20, 10);
+        assert_eq!(popup.clamp_within(screen), Rect::new(60, 14, 20, 10));
+
+        let huge = Rect::new(10, 10, 200, 200);
+        assert_eq!(huge.clamp_within(screen), screen);
+
+        let edge = Rect::new(65530, 0, 10, 1);
+        assert_eq!(
+            Rect::new(0, 0, 10, 1).clamp_within(edge),
+            Rect::new(65530, 0, 5, 1)
+        );
+    }
+
+    #[test]
+    fn test_rect_centered() {
+        let rect = Rect::new(0, 0, 80, 24);
+        assert_eq!(rect.centered(40, 10), Rect::new(20, 7, 40, 10));
+        assert_eq!(rect.centered(100, 100), rect);
+
+        let edge = Rect::new(65530, 0, 100, 10);
+        assert_eq!(edge.centered(4, 4), Rect::new(65530, 3, 4, 4));
+    }
+
+    #[test]
+    fn test_rect_rows_and_columns() {
+        let rect = Rect::new(1, 2, 3, 2);
+        let rows: Vec<Rect> = rect.rows().collect();
+        assert_eq!(rows, vec![Rect::new(1, 2, 3, 1), Rect::new(1, 3, 3, 1)]);
+
+        let columns: Vec<Rect> = rect.columns().rev().collect();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0], Rect::new(3, 2, 1, 2));
+
+        let positions: Vec<(u16, u16)> = rect.positions().collect();
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[0], (1, 2));
+        assert_eq!(positions[5], (3, 3));
+
+        let edge = Rect::new(0, 65530, 3, 100);
+        assert_eq!(edge.rows().count(), 5);
+        assert_eq!(edge.rows().next_back(), Some(Rect::new(0, 65534, 3, 1)));
+        let edge = Rect::new(65530, 0, 100, 1);
+        assert_eq!(edge.columns().len(), 5);
+        assert_eq!(edge.columns().next_back(), Some(Rect::new(65534, 0, 1, 1)));
+    }
+
+    #[test]
+    fn test_rect_splitting() {
+        let rect = Rect::new(0, 0, 10, 10);
+        assert_eq!(
+            rect.split_at_x(3),
+            (Rect::new(0, 0, 3, 10), Rect::new(3, 0, 7, 10))
+        );
+        assert_eq!(rect.split_at_y(20), (rect, Rect::new(0, 10, 10, 0)));
+
+        let edge = Rect::new(65530, 65530, 100, 100);
+        assert_eq!(edge.split_at_x(20).1, Rect::new(u16::MAX, 65530, 0, 5));
+        assert_eq!(edge.split_at_y(2).1, Rect::new(65530, 65532, 5, 3));
+
+        let mut area = rect;
+        let header = area.take_top(1);
+        let footer = area.take_bottom(2);
+        let sidebar = area.take_left(3);
+        assert_eq!(header, Rect::new(0, 0, 10, 1));
+        assert_eq!(footer, Rect::new(0, 8, 10, 2));
+        assert_eq!(sidebar, Rect::new(0, 1, 3, 7));
+        assert_eq!(area, Rect::new(3, 1, 7, 7));
+    }
 }
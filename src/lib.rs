@@ -32,23 +32,36 @@ use std::thread;
 use std::time::Duration;
 
 pub use crate::buffer::{Buffer, Cell};
-pub use crate::frame::Frame;
+pub use crate::frame::{Cursor, CursorShape, Frame};
+pub use crate::grid::{Grid, GridAreas};
+pub use crate::hitmap::HitMap;
 pub use crate::input::{Event, Input, KeyCode, KeyEvent, MouseEvent, MouseKind};
-pub use crate::layout::{Constraint, Direction, Layout, Rect};
+pub use crate::layout::{Constraint, Direction, Flex, Layout, Margin, Rect, Size};
 use crate::renderer::Renderer;
-pub use crate::style::{Color, Modifier, Style};
+pub use crate::responsive::{Breakpoint, Responsive};
+pub use crate::style::{Capabilities, Color, Modifier, ParseStyleError, Style, UnderlineStyle};
 use crate::terminal::Terminal;
-pub use crate::widgets::Widget;
+pub use crate::text::{Alignment, Line, Span, StyledText};
+pub use crate::theme::{Role, Theme};
+pub use crate::widgets::{Measure, StatefulWidget, Widget, WidgetRef};
 
+pub mod ansi;
 pub mod buffer;
+pub mod color;
 pub mod frame;
+pub mod grid;
+pub mod hitmap;
 pub mod input;
 #[macro_use]
 pub mod logger;
 pub mod layout;
+pub mod markup;
 pub mod renderer;
+pub mod responsive;
 pub mod style;
 pub mod terminal;
+pub mod text;
+pub mod theme;
 pub mod widgets;
 
 /// Commands returned by the application to control the runtime flow.
@@ -85,6 +98,18 @@ pub trait Application {
         None
     }
 
+    /// Maps a mouse event to an application-specific [`Self::Action`].
+    ///
+    /// `target` is the id of the topmost region under the cursor, as
+    /// registered with [`Frame::register_hit`] during the last
+    /// [`draw`](Self::draw). Override this to route clicks and hovers to
+    /// components; hovers also need [`wants_mouse_motion`](Self::wants_mouse_motion).
+    /// The default implementation forwards the event to
+    /// [`on_event`](Self::on_event).
+    fn on_mouse(&self, event: MouseEvent, _target: Option<&str>) -> Option<Self::Action> {
+        self.on_event(Event::Mouse(event))
+    }
+
     /// Updates the application state based on an action.
     ///
     /// This is the only place where you should modify your application state.
@@ -96,6 +121,23 @@ pub trait Application {
     /// The returned string will be drawn to the terminal. Use ANSI escape codes
     /// for colors and styling, or wait for the upcoming `Buffer` system!
     fn draw(&self, frame: &mut Frame);
+
+    /// Returns `true` to receive a [`MouseKind::Moved`] event on every
+    /// pointer move, e.g. to track hovers.
+    ///
+    /// Checked once at startup. The default is `false`: only clicks and
+    /// scrolling are reported, which avoids a redraw on each move.
+    fn wants_mouse_motion(&self) -> bool {
+        false
+    }
+
+    /// Returns the theme the frame is drawn with.
+    ///
+    /// Called before every [`draw`](Self::draw). Widgets use it for their
+    /// default styles; see [`Theme`]. The default is the empty theme.
+    fn theme(&self) -> Theme {
+        Theme::default()
+    }
 }
 
 /// Entry point to run a Phosphor application.
@@ -137,27 +179,44 @@ fn run_app<App: Application>(mut app: App, terminal: Terminal, mut input: Input)
         return Ok(());
     }
 
+    if app.wants_mouse_motion() {
+        terminal.enable_mouse_motion()?;
+    }
+
     let (width, height) = terminal.size()?;
-    let mut renderer = Renderer::new(width, height);
+    let mut renderer = Renderer::new(width, height).capabilities(Capabilities::detect());
+    // The back buffer is reused across frames; the renderer holds the front one.
+    let mut next_buffer = Buffer::new(width, height);
 
     // Initial screen clear
     terminal.write(b"\x1b[2J")?;
 
     loop {
         let (w, h) = terminal.size()?;
-        let mut next_buffer = Buffer::new(w, h);
+        next_buffer.resize(w, h);
+        next_buffer.reset();
         let screen = Rect::new(0, 0, w, h);
         let mut frame = Frame::new(&mut next_buffer, screen);
+        frame.set_theme(app.theme());
 
         // --- 1. Render Phase ---
         app.draw(&mut frame);
+        renderer.set_cursor(frame.cursor());
+        let hit_map = frame.into_hit_map();
         renderer.render(&terminal, &next_buffer)?;
 
         // --- 2. Input Phase ---
         let events = input.read(&terminal);
         for event in events {
-            // Map raw event -> App Action
-            if let Some(msg) = app.on_event(event) {
+            // Map raw event -> App Action, routing mouse events through the hit map
+            let action = match event {
+                Event::Mouse(mouse) => {
+                    let target = hit_map.resolve(&mouse);
+                    app.on_mouse(mouse, target)
+                }
+                event => app.on_event(event),
+            };
+            if let Some(msg) = action {
                 // Update State
                 match app.update(msg) {
                     Command::Quit => return Ok(()),
@@ -209,6 +268,7 @@ mod tests {
         // Arrange
         let mock = MockSystem::new();
         mock.push_input(b"q"); // Inject 'q' into the mock input buffer
+        let log_ref = mock.log.clone();
 
         // Inject the mock system into the Terminal
         let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
@@ -222,5 +282,55 @@ mod tests {
 
         // Assert
         assert!(res.is_ok());
+        // Only clicks are reported unless the app asks for motion.
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains("\x1b[?1000h")));
+        assert!(!log.iter().any(|s| s.contains("\x1b[?1003h")));
+    }
+
+    struct HitApp {
+        clicked: std::rc::Rc<std::cell::RefCell<Option<String>>>,
+    }
+
+    impl Application for HitApp {
+        type Action = Option<String>;
+
+        fn on_mouse(&self, _event: MouseEvent, target: Option<&str>) -> Option<Self::Action> {
+            Some(target.map(str::to_string))
+        }
+
+        fn wants_mouse_motion(&self) -> bool {
+            true
+        }
+
+        fn update(&mut self, target: Self::Action) -> Command {
+            *self.clicked.borrow_mut() = target;
+            Command::Quit
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            frame.register_hit(frame.area(), "screen");
+            frame.register_hit(Rect::new(10, 5, 4, 1), "button");
+        }
+    }
+
+    #[test]
+    fn test_run_loop_routes_mouse_to_hit_region() {
+        let mock = MockSystem::new();
+        // Left click at (11, 5)
+        mock.push_input(b"\x1b[M ,&");
+        let log_ref = mock.log.clone();
+
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let clicked = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let app = HitApp {
+            clicked: clicked.clone(),
+        };
+
+        run_app(app, terminal, Input::new()).unwrap();
+
+        assert_eq!(clicked.borrow().as_deref(), Some("button"));
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains("\x1b[?1003h")));
     }
 }
@@ -0,0 +1,356 @@
+//! The `markup` module parses a small inline style language into [`StyledText`].
+//!
+//! Tags in square brackets open a style, and `[/]` closes the most recently
+//! opened one. Tags can be nested; inner styles are layered over outer ones.
+//!
+//! ```text
+//! [bold red on #202020]Error:[/] file [underline]main.rs[/]
+//! ```
+//!
+//! A tag is a whitespace-separated list of:
+//! * **Modifiers**: `bold`, `dim`, `italic`, `underline`, `reversed`,
+//!   `strikethrough`, `blink`, `rapid_blink`, `hidden`, `overline`.
+//! * **Foreground colors**: anything [`Color`]'s `FromStr` accepts: a name
+//!   (`red`, `bright_blue`, `reset`, ...), a hex code (`#ff8800`), `rgb(r,g,b)`
+//!   without spaces or a 256-color index (`208`).
+//! * **Background colors**: `on` followed by a color.
+//!
+//! A closing tag may repeat the opening tag (`[bold]x[/bold]`), in which case
+//! it must match. Use `\[`, `\]` and `\\` to write literal brackets and
+//! backslashes. Newlines in the input start a new [`Line`].
+//!
+//! # Example
+//! ```
+//! use phosphor::{Color, Modifier, markup};
+//!
+//! let name = "main.rs";
+//! let text = markup::parse(&format!("[bold red]Error:[/] {name}")).unwrap();
+//! let spans = &text.lines[0].spans;
+//! assert_eq!(spans[0].content, "Error:");
+//! assert_eq!(spans[0].style.foreground, Some(Color::Red));
+//! assert!(spans[0].style.modifiers.contains(Modifier::BOLD));
+//! assert_eq!(spans[1].content, " main.rs");
+//! ```
+
+use std::fmt;
+
+use crate::{Color, Line, Modifier, Span, Style, StyledText};
+
+/// The reason a markup string failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupErrorKind {
+    /// A `[` was never closed by a `]`.
+    UnterminatedTag,
+    /// A tag with no content, `[]`.
+    EmptyTag,
+    /// A word in a tag that isn't a modifier or a color.
+    UnknownStyle(String),
+    /// `on` was not followed by a color.
+    MissingBackground,
+    /// A closing tag with no open tag to close.
+    UnexpectedClose,
+    /// A named closing tag that doesn't match the open tag.
+    MismatchedClose {
+        /// The tag that is currently open.
+        expected: String,
+        /// The tag named by the closing tag.
+        found: String,
+    },
+    /// A `]` outside of a tag; write `\]` for a literal bracket.
+    UnexpectedBracket,
+    /// A backslash followed by a character that can't be escaped.
+    InvalidEscape(char),
+    /// A backslash at the very end of the input.
+    DanglingEscape,
+}
+
+/// An error produced by [`parse`], with the byte offset where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    /// The byte offset in the input where the error was detected.
+    pub position: usize,
+    /// What went wrong.
+    pub kind: MarkupErrorKind,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MarkupErrorKind::UnterminatedTag => write!(f, "unterminated tag")?,
+            MarkupErrorKind::EmptyTag => write!(f, "empty tag")?,
+            MarkupErrorKind::UnknownStyle(s) => write!(f, "unknown style '{}'", s)?,
+            MarkupErrorKind::MissingBackground => write!(f, "expected a color after 'on'")?,
+            MarkupErrorKind::UnexpectedClose => write!(f, "closing tag without an open tag")?,
+            MarkupErrorKind::MismatchedClose { expected, found } => write!(
+                f,
+                "closing tag '[/{}]' does not match open tag '[{}]'",
+                found, expected
+            )?,
+            MarkupErrorKind::UnexpectedBracket => write!(f, "unexpected ']'")?,
+            MarkupErrorKind::InvalidEscape(c) => write!(f, "invalid escape '\\{}'", c)?,
+            MarkupErrorKind::DanglingEscape => write!(f, "backslash at end of input")?,
+        }
+        write!(f, " at byte {}", self.position)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// Parses a markup string into styled text.
+///
+/// Tags left open at the end of the input are closed implicitly.
+///
+/// # Errors
+/// Returns a [`MarkupError`] pointing at the offending byte for malformed
+/// tags, unknown style words, unbalanced closing tags and invalid escapes.
+pub fn parse(input: &str) -> Result<StyledText, MarkupError> {
+    let error = |position, kind| Err(MarkupError { position, kind });
+
+    let mut stack: Vec<(String, Style)> = Vec::new();
+    let mut lines = vec![Line::default()];
+    let mut text = String::new();
+    let mut chars = input.char_indices();
+
+    let current_style = |stack: &[(String, Style)]| stack.last().map_or(Style::default(), |s| s.1);
+
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, e @ ('[' | ']' | '\\'))) => text.push(e),
+                Some((_, e)) => return error(pos, MarkupErrorKind::InvalidEscape(e)),
+                None => return error(pos, MarkupErrorKind::DanglingEscape),
+            },
+            '\n' => {
+                flush(&mut lines, &mut text, current_style(&stack));
+                lines.push(Line::default());
+            }
+            ']' => return error(pos, MarkupErrorKind::UnexpectedBracket),
+            '[' => {
+                let start = pos + 1;
+                let end = loop {
+                    match chars.next() {
+                        Some((i, ']')) => break i,
+                        Some((i, '[')) => return error(i, MarkupErrorKind::UnterminatedTag),
+                        Some(_) => {}
+                        None => return error(pos, MarkupErrorKind::UnterminatedTag),
+                    }
+                };
+                let raw = &input[start..end];
+                let tag = raw.trim();
+                // Word positions in `tag` are relative to its trimmed start.
+                let tag_start = start + (raw.len() - raw.trim_start().len());
+
+                flush(&mut lines, &mut text, current_style(&stack));
+
+                if let Some(name) = tag.strip_prefix('/') {
+                    let name = name.trim();
+                    let Some((open, _)) = stack.last() else {
+                        return error(pos, MarkupErrorKind::UnexpectedClose);
+                    };
+                    if !name.is_empty() && name != open {
+                        return error(
+                            pos,
+                            MarkupErrorKind::MismatchedClose {
+                                expected: open.clone(),
+                                found: name.to_string(),
+                            },
+                        );
+                    }
+                    stack.pop();
+                } else {
+                    let style = parse_tag(tag, tag_start)?;
+                    let merged = current_style(&stack).patch(style);
+                    stack.push((tag.to_string(), merged));
+                }
+            }
+            c => text.push(c),
+        }
+    }
+
+    flush(&mut lines, &mut text, current_style(&stack));
+    Ok(StyledText::new(lines))
+}
+
+impl StyledText {
+    /// Parses a markup string into styled text. See the [`markup`](crate::markup) module.
+    ///
+    /// # Errors
+    /// Returns a [`MarkupError`] if the markup is malformed.
+    pub fn from_markup(input: &str) -> Result<Self, MarkupError> {
+        parse(input)
+    }
+}
+
+/// Helper to move pending text into the last line as a span, merging it
+/// with the previous span when the styles match.
+fn flush(lines: &mut [Line], text: &mut String, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    let line = lines.last_mut().expect("at least one line");
+    match line.spans.last_mut() {
+        Some(last) if last.style == style => last.content.push_str(text),
+        _ => line.spans.push(Span::styled(text.clone(), style)),
+    }
+    text.clear();
+}
+
+/// Parses the inside of an opening tag. `offset` is the byte offset of the
+/// tag content in the input, used for error positions.
+fn parse_tag(tag: &str, offset: usize) -> Result<Style, MarkupError> {
+    if tag.is_empty() {
+        return Err(MarkupError {
+            position: offset,
+            kind: MarkupErrorKind::EmptyTag,
+        });
+    }
+
+    let mut style = Style::default();
+    let mut words = tag
+        .split_whitespace()
+        .map(|w| (w.as_ptr() as usize - tag.as_ptr() as usize + offset, w));
+
+    while let Some((pos, word)) = words.next() {
+        let lower = word.to_ascii_lowercase();
+        if lower == "on" {
+            let Some((bg_pos, bg)) = words.next() else {
+                return Err(MarkupError {
+                    position: pos,
+                    kind: MarkupErrorKind::MissingBackground,
+                });
+            };
+            let color = bg.parse::<Color>().map_err(|_| MarkupError {
+                position: bg_pos,
+                kind: MarkupErrorKind::UnknownStyle(bg.to_string()),
+            })?;
+            style = style.bg(color);
+        } else if let Ok(modifier) = lower.parse::<Modifier>() {
+            style = style.modifier(modifier);
+        } else if let Ok(color) = word.parse::<Color>() {
+            style = style.fg(color);
+        } else {
+            return Err(MarkupError {
+                position: pos,
+                kind: MarkupErrorKind::UnknownStyle(word.to_string()),
+            });
+        }
+    }
+
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &StyledText) -> Vec<(&str, Style)> {
+        text.lines
+            .iter()
+            .flat_map(|l| &l.spans)
+            .map(|s| (s.content.as_str(), s.style))
+            .collect()
+    }
+
+    #[test]
+    fn test_markup_basic_tags() {
+        let text = parse("[bold red on #202020]Error:[/] file [underline]x[/]").unwrap();
+        let error_style = Style::new()
+            .fg(Color::Red)
+            .bg(Color::Rgb(0x20, 0x20, 0x20))
+            .modifier(Modifier::BOLD);
+
+        assert_eq!(
+            spans(&text),
+            vec![
+                ("Error:", error_style),
+                (" file ", Style::default()),
+                ("x", Style::new().modifier(Modifier::UNDERLINE)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markup_nesting() {
+        let text = parse("[red]a[bold]b[blue]c[/]d[/bold]e[/]f").unwrap();
+        let red = Style::new().fg(Color::Red);
+        let red_bold = red.modifier(Modifier::BOLD);
+
+        assert_eq!(
+            spans(&text),
+            vec![
+                ("a", red),
+                ("b", red_bold),
+                ("c", Style::new().fg(Color::Blue).modifier(Modifier::BOLD)),
+                ("d", red_bold),
+                ("e", red),
+                ("f", Style::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markup_escapes_and_lines() {
+        let text = parse("\\[not a tag\\] \\\\\n[208]two").unwrap();
+        assert_eq!(text.height(), 2);
+        assert_eq!(text.lines[0].spans[0].content, "[not a tag] \\");
+        assert_eq!(
+            text.lines[1].spans[0].style.foreground,
+            Some(Color::Indexed(208))
+        );
+    }
+
+    #[test]
+    fn test_markup_unclosed_tags_are_closed_at_end() {
+        let text = parse("[italic]open").unwrap();
+        assert_eq!(
+            spans(&text),
+            vec![("open", Style::new().modifier(Modifier::ITALIC))]
+        );
+    }
+
+    #[test]
+    fn test_markup_errors_report_positions() {
+        let err = |s: &str| parse(s).unwrap_err();
+
+        assert_eq!(
+            err("ab [bold"),
+            MarkupError {
+                position: 3,
+                kind: MarkupErrorKind::UnterminatedTag
+            }
+        );
+        assert_eq!(
+            err("[bold purple]x"),
+            MarkupError {
+                position: 6,
+                kind: MarkupErrorKind::UnknownStyle("purple".into())
+            }
+        );
+        assert_eq!(
+            err("[  purple]x"),
+            MarkupError {
+                position: 3,
+                kind: MarkupErrorKind::UnknownStyle("purple".into())
+            }
+        );
+        assert_eq!(err("[ red on  nope]").position, 10);
+        assert_eq!(err("x[/]").position, 1);
+        assert_eq!(err("x[/]").kind, MarkupErrorKind::UnexpectedClose);
+        assert_eq!(err("[]").kind, MarkupErrorKind::EmptyTag);
+        assert_eq!(err("[red on]").kind, MarkupErrorKind::MissingBackground);
+        assert_eq!(err("a]").kind, MarkupErrorKind::UnexpectedBracket);
+        assert_eq!(err("\\q").kind, MarkupErrorKind::InvalidEscape('q'));
+        assert_eq!(err("ab\\").kind, MarkupErrorKind::DanglingEscape);
+        assert_eq!(
+            err("[bold]x[/italic]").kind,
+            MarkupErrorKind::MismatchedClose {
+                expected: "bold".into(),
+                found: "italic".into()
+            }
+        );
+        assert_eq!(
+            err("[bold purple]").to_string(),
+            "unknown style 'purple' at byte 6"
+        );
+    }
+}
@@ -3,50 +3,157 @@
 //! It uses a [`Buffer`] to track the current state of the
 //! screen and only sends the minimal set of ANSI escape codes to update it.
 
-use crate::buffer::Buffer;
+use crate::buffer::{Buffer, Scroll};
+use crate::frame::Cursor;
+use crate::style::Capabilities;
 use crate::terminal::Terminal;
+use crate::text::char_width;
 use std::io;
 
 /// The primary rendering engine.
 pub struct Renderer {
     /// The state of the terminal as of the last render.
     current_buffer: Buffer,
+    /// The optional features the terminal supports.
+    capabilities: Capabilities,
+    /// The cursor to show after the next render.
+    cursor: Option<Cursor>,
+    /// The cursor shown by the last render, if any.
+    shown_cursor: Option<Cursor>,
+    /// The DECSCUSR sequence last sent to the terminal.
+    cursor_shape: Option<String>,
 }
 
 impl Renderer {
     /// Creates a new renderer for a terminal of the given size.
+    ///
+    /// Every optional style feature is assumed to be supported; use
+    /// [`Renderer::capabilities`] to restrict them.
     pub fn new(width: u16, height: u16) -> Self {
         Self {
             current_buffer: Buffer::new(width, height),
+            capabilities: Capabilities::all(),
+            cursor: None,
+            shown_cursor: None,
+            cursor_shape: None,
         }
     }
 
+    /// Sets the optional terminal features the renderer may use.
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Sets the cursor to show after each render, or `None` to hide it.
+    ///
+    /// This is usually [`Frame::cursor`](crate::Frame::cursor) of the frame
+    /// that drew the buffer.
+    pub fn set_cursor(&mut self, cursor: Option<Cursor>) {
+        self.cursor = cursor;
+    }
+
     /// Updates the terminal to match the state of the given buffer.
     ///
     /// This method calculates the difference between the new buffer and the
-    /// previous one, and only writes the changed cells to the terminal.
+    /// previous one, and only writes the changed cells to the terminal. Only
+    /// rows that are dirty in either buffer are compared (see
+    /// [`Buffer::diff_dirty`]).
     pub fn render(&mut self, terminal: &Terminal, next: &Buffer) -> io::Result<()> {
         // TODO: Implement diff-styling to only send ANSI style codes when they change.
         // If buffers sizes are different, clear the screen
         if next.width != self.current_buffer.width || next.height != self.current_buffer.height {
+            self.hide_cursor(terminal)?;
             terminal.write("\x1b[2J".as_bytes())?;
         }
 
-        let diff = next.diff(&self.current_buffer);
+        // Move shifted rows with a scroll region so that only the newly
+        // exposed rows need to be repainted. Without left and right margins,
+        // only bands of full-width rows can be scrolled.
+        let scroll = next.detect_scroll(&self.current_buffer).or_else(|| {
+            let area = next.changed_area(&self.current_buffer)?;
+            let margins = self.capabilities.left_right_margins;
+            margins.then(|| next.detect_scroll_in(&self.current_buffer, area))?
+        });
+        if let Some(scroll) = scroll {
+            self.hide_cursor(terminal)?;
+            terminal.write(scroll_sequence(scroll, next.width).as_bytes())?;
+            self.current_buffer.scroll_rows(scroll);
+        }
+
+        let diff = next.diff_dirty(&self.current_buffer);
+
+        if !diff.is_empty() {
+            self.hide_cursor(terminal)?;
+        }
 
         for change in diff {
+            // Skip the blank cell covered by the right half of a wide character.
+            if change.x > 0 && char_width(next.get(change.x - 1, change.y).symbol) == 2 {
+                continue;
+            }
             terminal.write(format!("\x1b[{};{}H", change.y + 1, change.x + 1).as_bytes())?;
-            terminal.write(change.cell.style.to_ansi().as_bytes())?;
+            let style = change.cell.style.to_ansi_with(self.capabilities);
+            terminal.write(style.as_bytes())?;
             let mut buf = [0u8; 4];
             terminal.write(change.cell.symbol.encode_utf8(&mut buf).as_bytes())?;
         }
 
-        self.current_buffer = next.clone();
+        // Reuses the existing allocation when the sizes match.
+        self.current_buffer.clone_from(next);
+
+        self.render_cursor(terminal)
+    }
 
+    /// Helper to hide the cursor if it is shown, e.g. while it jumps around
+    /// the changed cells.
+    fn hide_cursor(&mut self, terminal: &Terminal) -> io::Result<()> {
+        if self.shown_cursor.take().is_some() {
+            terminal.write(b"\x1b[?25l")?;
+        }
+        Ok(())
+    }
+
+    /// Helper to show the requested cursor once the cells are drawn, or to
+    /// hide it if none was requested.
+    fn render_cursor(&mut self, terminal: &Terminal) -> io::Result<()> {
+        let cursor = self
+            .cursor
+            .filter(|c| c.x < self.current_buffer.width && c.y < self.current_buffer.height);
+        let Some(cursor) = cursor else {
+            return self.hide_cursor(terminal);
+        };
+
+        let shape = cursor.shape_ansi();
+        if self.cursor_shape.as_ref() != Some(&shape) {
+            terminal.write(shape.as_bytes())?;
+            self.cursor_shape = Some(shape);
+        }
+        if self.shown_cursor != Some(cursor) {
+            terminal.write(format!("\x1b[{};{}H", cursor.y + 1, cursor.x + 1).as_bytes())?;
+            if self.shown_cursor.is_none() {
+                terminal.write(b"\x1b[?25h")?;
+            }
+            self.shown_cursor = Some(cursor);
+        }
         Ok(())
     }
 }
 
+/// Helper to build the sequence scrolling a region, with left and right
+/// margins (DECSLRM) if it is narrower than the screen.
+fn scroll_sequence(scroll: Scroll, width: u16) -> String {
+    let direction = if scroll.amount > 0 { 'S' } else { 'T' };
+    let lines = scroll.amount.unsigned_abs();
+    let rows = format!("\x1b[{};{}r", scroll.top + 1, scroll.bottom);
+    if scroll.left == 0 && scroll.right >= width {
+        format!("\x1b[0m{rows}\x1b[{lines}{direction}\x1b[r")
+    } else {
+        let columns = format!("\x1b[{};{}s", scroll.left + 1, scroll.right);
+        format!("\x1b[0m\x1b[?69h{rows}{columns}\x1b[{lines}{direction}\x1b[r\x1b[?69l")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +182,192 @@ mod tests {
         // Check for the style code: Reset(0), Red(31)
         assert!(log.iter().any(|s| s.contains("0;31")));
     }
+
+    #[test]
+    fn test_renderer_erases_rows_drawn_last_frame() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 3);
+
+        let mut buffer = Buffer::new(3, 3);
+        buffer.set(0, 2, 'X');
+        renderer.render(&terminal, &buffer).unwrap();
+
+        // Next frame reuses the buffer and draws nothing.
+        buffer.reset();
+        log_ref.lock().unwrap().clear();
+        renderer.render(&terminal, &buffer).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains("\x1b[3;1H")));
+        assert!(!log.iter().any(|s| s.contains('X')));
+    }
+
+    #[test]
+    fn test_renderer_skips_wide_char_continuation() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 1);
+
+        let mut next = Buffer::new(3, 1);
+        next.set(0, 0, '漢');
+        next.set(1, 0, 'x'); // covered by the wide char
+        next.set(2, 0, 'y');
+        renderer.render(&terminal, &next).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains('漢')));
+        assert!(!log.iter().any(|s| s.contains("\x1b[1;2H")));
+        assert!(log.iter().any(|s| s.contains("\x1b[1;3H")));
+    }
+
+    #[test]
+    fn test_renderer_applies_capability_fallbacks() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(1, 1).capabilities(Capabilities::basic());
+
+        let mut next = Buffer::new(1, 1);
+        let style = Style::new()
+            .underline_style(crate::UnderlineStyle::Curly)
+            .underline_color(Color::Red);
+        next.set_with_style(0, 0, 'x', style);
+        renderer.render(&terminal, &next).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains("\x1b[0;4m")));
+        assert!(!log.iter().any(|s| s.contains("4:3") || s.contains("58;")));
+    }
+
+    #[test]
+    fn test_renderer_shows_and_hides_cursor() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(4, 2);
+        let mut buffer = Buffer::new(4, 2);
+        // Renders and returns everything written to the terminal.
+        let render = |renderer: &mut Renderer, buffer: &Buffer| -> String {
+            log_ref.lock().unwrap().clear();
+            renderer.render(&terminal, buffer).unwrap();
+            let log = log_ref.lock().unwrap();
+            log.iter()
+                .filter_map(|s| s.strip_prefix("write(100, \"")?.strip_suffix("\")"))
+                .collect()
+        };
+
+        let mut cursor = Cursor::new(2, 1);
+        cursor.shape = crate::CursorShape::Bar;
+        renderer.set_cursor(Some(cursor));
+        assert_eq!(render(&mut renderer, &buffer), "\x1b[5 q\x1b[2;3H\x1b[?25h");
+
+        // Nothing changed, nothing is written.
+        assert_eq!(render(&mut renderer, &buffer), "");
+
+        // Drawing hides the cursor until the cells are written.
+        buffer.set(0, 0, 'x');
+        let written = render(&mut renderer, &buffer);
+        assert!(written.starts_with("\x1b[?25l"));
+        assert!(written.ends_with("x\x1b[2;3H\x1b[?25h"));
+
+        renderer.set_cursor(None);
+        assert_eq!(render(&mut renderer, &buffer), "\x1b[?25l");
+    }
+
+    #[test]
+    fn test_renderer_ignores_removed_modifiers() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(2, 1);
+
+        let mut buffer = Buffer::new(2, 1);
+        buffer.set(0, 0, 'x');
+        renderer.render(&terminal, &buffer).unwrap();
+
+        // The same cell, reached through a patch that removes a modifier.
+        let mut frame = crate::Frame::new(&mut buffer, crate::Rect::new(0, 0, 2, 1));
+        let style = Style::new().remove_modifier(crate::Modifier::BOLD);
+        frame.write_str_with_style(0, 0, "x", style);
+        log_ref.lock().unwrap().clear();
+        renderer.render(&terminal, &buffer).unwrap();
+
+        assert!(log_ref.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_renderer_uses_scroll_region() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 4);
+
+        let mut buffer = Buffer::new(3, 4);
+        for (y, c) in ['a', 'b', 'c', 'd'].into_iter().enumerate() {
+            buffer.set(0, y as u16, c);
+        }
+        renderer.render(&terminal, &buffer).unwrap();
+
+        // The log scrolls by one line.
+        buffer.reset();
+        for (y, c) in ['b', 'c', 'd', 'e'].into_iter().enumerate() {
+            buffer.set(0, y as u16, c);
+        }
+        log_ref.lock().unwrap().clear();
+        renderer.render(&terminal, &buffer).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert!(log.iter().any(|s| s.contains("\x1b[1;4r\x1b[1S\x1b[r")));
+        // Only the exposed bottom row is repainted.
+        let cursor_moves = log.iter().filter(|s| s.contains('H')).count();
+        assert_eq!(cursor_moves, 1);
+        assert!(log.iter().any(|s| s.contains("\x1b[4;1H")));
+        assert!(log.iter().any(|s| s.contains('e')));
+    }
+
+    #[test]
+    fn test_renderer_scrolls_pane_next_to_sidebar() {
+        // A static sidebar in column 0 and a log pane in columns 1 and 2.
+        let pane = |lines: [char; 4]| {
+            let mut buffer = Buffer::new(3, 4);
+            for (y, c) in lines.into_iter().enumerate() {
+                buffer.set(0, y as u16, char::from(b'0' + y as u8));
+                buffer.set(1, y as u16, c);
+                buffer.set(2, y as u16, c);
+            }
+            buffer
+        };
+        let render = |capabilities: Capabilities| {
+            let mock = MockSystem::new();
+            let log_ref = mock.log.clone();
+            let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+            let mut renderer = Renderer::new(3, 4).capabilities(capabilities);
+            renderer
+                .render(&terminal, &pane(['a', 'b', 'c', 'd']))
+                .unwrap();
+            log_ref.lock().unwrap().clear();
+            renderer
+                .render(&terminal, &pane(['b', 'c', 'd', 'e']))
+                .unwrap();
+            let log = log_ref.lock().unwrap();
+            log.clone()
+        };
+
+        let log = render(Capabilities::all());
+        assert!(
+            log.iter()
+                .any(|s| s.contains("\x1b[?69h\x1b[1;4r\x1b[2;3s\x1b[1S\x1b[r\x1b[?69l"))
+        );
+        let cursor_moves = log.iter().filter(|s| s.contains('H')).count();
+        assert_eq!(cursor_moves, 2);
+
+        // Without margins, every changed cell of the pane is repainted.
+        let log = render(Capabilities::basic());
+        assert!(!log.iter().any(|s| s.contains("69h")));
+        let cursor_moves = log.iter().filter(|s| s.contains('H')).count();
+        assert_eq!(cursor_moves, 8);
+    }
 }
@@ -0,0 +1,206 @@
+//! The `responsive` module picks a layout based on the size of the area.
+//!
+//! A [`Responsive`] holds a base value and any number of alternatives, each
+//! used from a minimum size up. It works with anything, but is meant for
+//! [`Layout`]s and [`Grid`]s, for which it can split an area directly.
+//!
+//! Panes that only make sense on large screens can also be hidden with
+//! [`Layout::optional`], which keeps the number of areas fixed.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Constraint, Direction, Layout, Rect, Responsive};
+//!
+//! let layout = Responsive::new(Layout::new(
+//!     Direction::Vertical,
+//!     vec![Constraint::Length(10), Constraint::Fill],
+//! ))
+//! .min_width(
+//!     100,
+//!     Layout::new(
+//!         Direction::Horizontal,
+//!         vec![Constraint::Length(30), Constraint::Fill],
+//!     ),
+//! );
+//!
+//! let [nav, body] = layout.split_to(Rect::new(0, 0, 60, 40));
+//! assert_eq!((nav, body), (Rect::new(0, 0, 60, 10), Rect::new(0, 10, 60, 30)));
+//!
+//! let [nav, body] = layout.split_to(Rect::new(0, 0, 120, 40));
+//! assert_eq!((nav, body), (Rect::new(0, 0, 30, 40), Rect::new(30, 0, 90, 40)));
+//! ```
+
+use crate::{Grid, GridAreas, Layout, Rect, Size};
+
+/// A named range of terminal widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Narrower than [`Breakpoint::REGULAR`] columns, e.g. a split pane.
+    Compact,
+    /// From [`Breakpoint::REGULAR`] up to [`Breakpoint::WIDE`] columns.
+    Regular,
+    /// At least [`Breakpoint::WIDE`] columns.
+    Wide,
+}
+
+impl Breakpoint {
+    /// The width at which [`Breakpoint::Regular`] starts.
+    pub const REGULAR: u16 = 80;
+    /// The width at which [`Breakpoint::Wide`] starts.
+    pub const WIDE: u16 = 160;
+
+    /// Returns the breakpoint for the width of `area`.
+    pub fn of(area: Rect) -> Self {
+        match area.width {
+            w if w >= Self::WIDE => Breakpoint::Wide,
+            w if w >= Self::REGULAR => Breakpoint::Regular,
+            _ => Breakpoint::Compact,
+        }
+    }
+}
+
+/// A value that changes with the size of the area, such as a [`Layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Responsive<T> {
+    /// The value used when no breakpoint matches.
+    base: T,
+    /// The alternatives with the minimum size they need, in insertion order.
+    breakpoints: Vec<(Size, T)>,
+}
+
+impl<T> Responsive<T> {
+    /// Creates a responsive value that always picks `base`.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Creates a responsive value from one value per [`Breakpoint`].
+    pub fn breakpoints(compact: T, regular: T, wide: T) -> Self {
+        Self::new(compact)
+            .min_width(Breakpoint::REGULAR, regular)
+            .min_width(Breakpoint::WIDE, wide)
+    }
+
+    /// Uses `value` when the area is at least `width` columns wide.
+    pub fn min_width(self, width: u16, value: T) -> Self {
+        self.min_size(width, 0, value)
+    }
+
+    /// Uses `value` when the area is at least `height` rows high.
+    pub fn min_height(self, height: u16, value: T) -> Self {
+        self.min_size(0, height, value)
+    }
+
+    /// Uses `value` when the area is at least `width` by `height` cells.
+    ///
+    /// When several breakpoints match, the one added last wins, so add them
+    /// from the smallest to the largest.
+    pub fn min_size(mut self, width: u16, height: u16, value: T) -> Self {
+        self.breakpoints.push((Size::new(width, height), value));
+        self
+    }
+
+    /// Returns the value for the size of `area`.
+    pub fn pick(&self, area: Rect) -> &T {
+        self.breakpoints
+            .iter()
+            .rev()
+            .find(|(min, _)| area.width >= min.width && area.height >= min.height)
+            .map_or(&self.base, |(_, value)| value)
+    }
+}
+
+impl Responsive<Layout> {
+    /// Splits `area` with the layout picked for its size.
+    pub fn split(&self, area: Rect) -> Vec<Rect> {
+        self.pick(area).split(area)
+    }
+
+    /// Splits `area` into a fixed-size array with the layout picked for its
+    /// size.
+    ///
+    /// # Panics
+    /// Panics if the picked layout doesn't have `N` constraints.
+    pub fn split_to<const N: usize>(&self, area: Rect) -> [Rect; N] {
+        self.pick(area).split_to(area)
+    }
+}
+
+impl Responsive<Grid> {
+    /// Splits `area` with the grid picked for its size.
+    pub fn split(&self, area: Rect) -> GridAreas {
+        self.pick(area).split(area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constraint, Direction};
+
+    #[test]
+    fn test_breakpoint_of() {
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 60, 10)), Breakpoint::Compact);
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 80, 10)), Breakpoint::Regular);
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 300, 10)), Breakpoint::Wide);
+    }
+
+    #[test]
+    fn test_responsive_pick() {
+        let r = Responsive::breakpoints("compact", "regular", "wide").min_size(200, 50, "huge");
+
+        assert_eq!(*r.pick(Rect::new(0, 0, 79, 100)), "compact");
+        assert_eq!(*r.pick(Rect::new(0, 0, 80, 1)), "regular");
+        assert_eq!(*r.pick(Rect::new(0, 0, 250, 49)), "wide");
+        assert_eq!(*r.pick(Rect::new(0, 0, 250, 50)), "huge");
+        assert_eq!(
+            *Responsive::new(1)
+                .min_height(10, 2)
+                .pick(Rect::new(0, 0, 0, 9)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_responsive_layout_hides_optional_panes() {
+        let layout = Responsive::new(
+            Layout::new(
+                Direction::Horizontal,
+                vec![Constraint::Length(20), Constraint::Fill],
+            )
+            .spacing(1)
+            .optional(0, 60),
+        )
+        .min_width(
+            120,
+            Layout::new(Direction::Horizontal, vec![Constraint::Length(20); 3]).spacing(1),
+        );
+
+        let [nav, body] = layout.split_to(Rect::new(0, 0, 59, 5));
+        assert_eq!(nav, Rect::new(0, 0, 0, 5));
+        assert_eq!(body, Rect::new(0, 0, 59, 5));
+
+        let [nav, body] = layout.split_to(Rect::new(0, 0, 60, 5));
+        assert_eq!(nav, Rect::new(0, 0, 20, 5));
+        assert_eq!(body, Rect::new(21, 0, 39, 5));
+
+        assert_eq!(layout.split(Rect::new(0, 0, 120, 5)).len(), 3);
+    }
+
+    #[test]
+    fn test_responsive_grid() {
+        let grid = Responsive::new(Grid::new(vec![Constraint::Fill; 2], vec![Constraint::Fill]))
+            .min_width(
+                100,
+                Grid::new(vec![Constraint::Fill], vec![Constraint::Fill; 2]),
+            );
+
+        let areas = grid.split(Rect::new(0, 0, 60, 20));
+        assert_eq!((areas.rows(), areas.columns()), (2, 1));
+        let areas = grid.split(Rect::new(0, 0, 100, 20));
+        assert_eq!((areas.rows(), areas.columns()), (1, 2));
+    }
+}
@@ -1,9 +1,25 @@
 //! The `style` module provides types for customizing the appearance of text.
 //!
 //! It supports ANSI colors and text modifiers like Bold, Italic, and Underline.
+//! Extended underline shapes and colors are emitted only when the terminal
+//! supports them (see [`Capabilities`]).
+//!
+//! [`Color`], [`Modifier`] and [`Style`] implement `FromStr` and `Display`,
+//! so they can be read from config files and command line flags:
+//!
+//! ```
+//! use phosphor::{Color, Modifier, Style};
+//!
+//! let style: Style = "bold yellow on #202020".parse().unwrap();
+//! assert_eq!(style, Style::new().modifier(Modifier::BOLD).fg(Color::Yellow).bg(Color::Rgb(32, 32, 32)));
+//! assert_eq!(style.to_string(), "bold yellow on #202020");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents a color in the terminal.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     Reset,
     Black,
@@ -87,10 +103,74 @@ impl Color {
             Color::Rgb(r, g, b) => format!("48;2;{};{};{}", r, g, b),
         }
     }
+
+    /// Returns the SGR parameters that set this color as the underline color.
+    ///
+    /// Named colors are sent as their 256-color palette index, since `58` has
+    /// no short form.
+    pub fn to_ansi_underline(&self) -> String {
+        match self {
+            Color::Reset => "59".to_string(),
+            Color::Indexed(i) => format!("58;5;{}", i),
+            Color::Rgb(r, g, b) => format!("58;2;{};{};{}", r, g, b),
+            named => format!("58;5;{}", named.ansi_index().unwrap_or(0)),
+        }
+    }
+
+    /// Helper to map a named color to its index in the 16-color palette.
+    fn ansi_index(&self) -> Option<u8> {
+        let index = match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::Reset | Color::Indexed(_) | Color::Rgb(..) => return None,
+        };
+        Some(index)
+    }
+}
+
+/// The shape of an underline.
+///
+/// Shapes other than [`UnderlineStyle::Single`] use the `4:x` form of the
+/// underline parameter, which older terminals may not understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnderlineStyle {
+    #[default]
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Returns the SGR parameter for this underline shape.
+    pub fn to_ansi(self) -> &'static str {
+        match self {
+            UnderlineStyle::Single => "4",
+            UnderlineStyle::Double => "4:2",
+            UnderlineStyle::Curly => "4:3",
+            UnderlineStyle::Dotted => "4:4",
+            UnderlineStyle::Dashed => "4:5",
+        }
+    }
 }
 
 /// A bitflag representing text modifiers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Modifier(u16);
 
 impl Modifier {
@@ -99,6 +179,11 @@ impl Modifier {
     pub const UNDERLINE: Self = Self(0b0000_0100);
     pub const REVERSED: Self = Self(0b0000_1000);
     pub const DIM: Self = Self(0b0001_0000);
+    pub const STRIKETHROUGH: Self = Self(0b0010_0000);
+    pub const SLOW_BLINK: Self = Self(0b0100_0000);
+    pub const RAPID_BLINK: Self = Self(0b1000_0000);
+    pub const HIDDEN: Self = Self(0b0001_0000_0000);
+    pub const OVERLINE: Self = Self(0b0010_0000_0000);
 
     pub fn empty() -> Self {
         Self(0)
@@ -111,6 +196,11 @@ impl Modifier {
     pub fn insert(&mut self, other: Self) {
         self.0 |= other.0;
     }
+
+    /// Removes the given modifiers from the set.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
 }
 
 impl std::ops::BitOr for Modifier {
@@ -120,12 +210,110 @@ impl std::ops::BitOr for Modifier {
     }
 }
 
+/// The optional terminal features a [`Style`] may use.
+///
+/// When a feature is missing, [`Style::to_ansi_with`] falls back to the
+/// closest basic rendering: shaped underlines become single underlines and
+/// the underline color is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// Supports underline shapes (`4:2` to `4:5`).
+    pub styled_underline: bool,
+    /// Supports underline colors (`58`).
+    pub underline_color: bool,
+    /// Supports left and right scroll margins (DECSLRM, `CSI ? 69 h`), which
+    /// let the renderer scroll a pane narrower than the screen.
+    pub left_right_margins: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Capabilities {
+    /// Every feature is supported.
+    pub fn all() -> Self {
+        Self {
+            styled_underline: true,
+            underline_color: true,
+            left_right_margins: true,
+        }
+    }
+
+    /// Only the basic SGR parameters are supported.
+    pub fn basic() -> Self {
+        Self {
+            styled_underline: false,
+            underline_color: false,
+            left_right_margins: false,
+        }
+    }
+
+    /// Guesses the capabilities of the current terminal from `TERM`,
+    /// `TERM_PROGRAM` and `VTE_VERSION`.
+    ///
+    /// Unknown terminals get [`Capabilities::basic`], because terminals
+    /// that do not understand `4:3` may read it as `4;3` (underline + italic).
+    /// Left and right margins are only enabled for terminals known to
+    /// support them; Alacritty and VTE don't.
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::detect_from(
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+            var("VTE_VERSION").as_deref(),
+        )
+    }
+
+    /// Helper to guess the capabilities from environment values.
+    fn detect_from(term: Option<&str>, program: Option<&str>, vte: Option<&str>) -> Self {
+        const TERMS: [&str; 6] = [
+            "kitty",
+            "wezterm",
+            "foot",
+            "ghostty",
+            "alacritty",
+            "contour",
+        ];
+        const PROGRAMS: [&str; 3] = ["WezTerm", "ghostty", "iTerm.app"];
+
+        let term = term.unwrap_or_default();
+        let known_term = TERMS.iter().any(|t| term.contains(t));
+        let known_program = program.is_some_and(|p| PROGRAMS.contains(&p));
+        // VTE (GNOME Terminal, Tilix, ...) supports both since 0.51.2.
+        let modern_vte = vte
+            .and_then(|v| v.parse::<u32>().ok())
+            .is_some_and(|v| v >= 5102);
+
+        const MARGIN_TERMS: [&str; 5] = ["kitty", "wezterm", "foot", "ghostty", "contour"];
+        let margins = MARGIN_TERMS.iter().any(|t| term.contains(t)) || known_program;
+
+        let styled = known_term || known_program || modern_vte;
+        Self {
+            styled_underline: styled,
+            underline_color: styled,
+            left_right_margins: margins,
+        }
+    }
+}
+
 /// Represents the visual style of a cell.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Unset colors (`None`) are inherited when a style is [patched](Style::patch)
+/// over another one. `modifiers` is the set of modifiers added by the style and
+/// `sub_modifiers` the set it removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Style {
     pub foreground: Option<Color>,
     pub background: Option<Color>,
     pub modifiers: Modifier,
+    pub sub_modifiers: Modifier,
+    /// The shape of the underline. `None` draws a single underline.
+    pub underline_style: Option<UnderlineStyle>,
+    /// The color of the underline. `None` uses the foreground color.
+    pub underline_color: Option<Color>,
 }
 
 impl Style {
@@ -148,13 +336,82 @@ impl Style {
 
     /// Adds a modifier.
     pub fn modifier(mut self, modifier: Modifier) -> Self {
+        self.sub_modifiers.remove(modifier);
         self.modifiers.insert(modifier);
         self
     }
 
-    // TODO: Implement a `patch` or `inherit` method to combine styles.
+    /// Sets the underline shape and adds the [`Modifier::UNDERLINE`] modifier.
+    pub fn underline_style(mut self, underline: UnderlineStyle) -> Self {
+        self.underline_style = Some(underline);
+        self.modifier(Modifier::UNDERLINE)
+    }
+
+    /// Sets the underline color.
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
+    /// Removes a modifier, including one inherited through [`Style::patch`].
+    pub fn remove_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers.remove(modifier);
+        self.sub_modifiers.insert(modifier);
+        self
+    }
+
+    /// Layers `other` on top of this style.
+    ///
+    /// Colors and the underline shape set in `other` replace the current ones,
+    /// unset ones are kept.
+    /// Modifiers removed by `other` are dropped, then its modifiers are added.
+    ///
+    /// # Example
+    /// ```
+    /// use phosphor::{Color, Modifier, Style};
+    ///
+    /// let base = Style::new().fg(Color::Blue).modifier(Modifier::ITALIC);
+    /// let patched = base.patch(
+    ///     Style::new()
+    ///         .modifier(Modifier::BOLD)
+    ///         .remove_modifier(Modifier::ITALIC),
+    /// );
+    /// assert_eq!(patched.foreground, Some(Color::Blue));
+    /// assert!(patched.modifiers.contains(Modifier::BOLD));
+    /// assert!(!patched.modifiers.contains(Modifier::ITALIC));
+    /// ```
+    pub fn patch(mut self, other: Style) -> Self {
+        self.foreground = other.foreground.or(self.foreground);
+        self.background = other.background.or(self.background);
+        self.underline_style = other.underline_style.or(self.underline_style);
+        self.underline_color = other.underline_color.or(self.underline_color);
 
+        self.modifiers.remove(other.sub_modifiers);
+        self.modifiers.insert(other.modifiers);
+        self.sub_modifiers.remove(other.modifiers);
+        self.sub_modifiers.insert(other.sub_modifiers);
+        self
+    }
+
+    /// Returns the style as drawn on screen, without the set of removed
+    /// modifiers.
+    ///
+    /// Removals only matter when patching, so cells store resolved styles:
+    /// two cells that look the same then compare equal.
+    pub fn resolved(mut self) -> Self {
+        self.sub_modifiers = Modifier::empty();
+        self
+    }
+
+    /// Returns the escape sequence that resets the terminal and applies
+    /// this style, assuming every optional feature is supported.
     pub fn to_ansi(&self) -> String {
+        self.to_ansi_with(Capabilities::all())
+    }
+
+    /// Returns the escape sequence for this style, falling back to basic
+    /// parameters for features missing from `capabilities`.
+    pub fn to_ansi_with(&self, capabilities: Capabilities) -> String {
         let mut codes = vec!["0".to_string()];
 
         if let Some(fg) = self.foreground {
@@ -163,26 +420,432 @@ impl Style {
         if let Some(bg) = self.background {
             codes.push(bg.to_ansi_bg());
         }
-        if self.modifiers.contains(Modifier::BOLD) {
+
+        let modifiers = self.modifiers;
+        if modifiers.contains(Modifier::BOLD) {
             codes.push("1".to_string());
         }
-        if self.modifiers.contains(Modifier::DIM) {
+        if modifiers.contains(Modifier::DIM) {
             codes.push("2".to_string());
         }
-        if self.modifiers.contains(Modifier::ITALIC) {
+        if modifiers.contains(Modifier::ITALIC) {
             codes.push("3".to_string());
         }
-        if self.modifiers.contains(Modifier::UNDERLINE) {
-            codes.push("4".to_string());
+        if modifiers.contains(Modifier::UNDERLINE) {
+            let underline = match self.underline_style {
+                Some(shape) if capabilities.styled_underline => shape,
+                _ => UnderlineStyle::Single,
+            };
+            codes.push(underline.to_ansi().to_string());
+        }
+        if modifiers.contains(Modifier::SLOW_BLINK) {
+            codes.push("5".to_string());
         }
-        if self.modifiers.contains(Modifier::REVERSED) {
+        if modifiers.contains(Modifier::RAPID_BLINK) {
+            codes.push("6".to_string());
+        }
+        if modifiers.contains(Modifier::REVERSED) {
             codes.push("7".to_string());
         }
+        if modifiers.contains(Modifier::HIDDEN) {
+            codes.push("8".to_string());
+        }
+        if modifiers.contains(Modifier::STRIKETHROUGH) {
+            codes.push("9".to_string());
+        }
+        if modifiers.contains(Modifier::OVERLINE) {
+            codes.push("53".to_string());
+        }
+        if let Some(color) = self.underline_color
+            && capabilities.underline_color
+            && modifiers.contains(Modifier::UNDERLINE)
+        {
+            codes.push(color.to_ansi_underline());
+        }
 
         format!("\x1b[{}m", codes.join(";"))
     }
 }
 
+/// An error produced when parsing a [`Color`], [`Modifier`] or [`Style`]
+/// from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseStyleError {
+    /// The input is empty.
+    Empty,
+    /// A word that is not a color name, hex code, `rgb(...)` or index.
+    UnknownColor(String),
+    /// A `#` code that is not `#rgb` or `#rrggbb`.
+    InvalidHex(String),
+    /// An `rgb(...)` that does not hold three values from 0 to 255.
+    InvalidRgb(String),
+    /// A word that is not a modifier name.
+    UnknownModifier(String),
+    /// An `underline:` value that is neither a shape nor a color.
+    UnknownUnderline(String),
+    /// A word in a style that is not a modifier, a color or `on`.
+    UnknownWord(String),
+    /// `on` was not followed by a color.
+    MissingBackground,
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseStyleError::Empty => write!(f, "empty input"),
+            ParseStyleError::UnknownColor(s) => write!(
+                f,
+                "unknown color '{}' (expected a name, #rrggbb, #rgb, rgb(r, g, b) or 0-255)",
+                s
+            ),
+            ParseStyleError::InvalidHex(s) => {
+                write!(f, "invalid hex color '{}' (expected #rgb or #rrggbb)", s)
+            }
+            ParseStyleError::InvalidRgb(s) => write!(
+                f,
+                "invalid rgb color '{}' (expected rgb(r, g, b) with values 0-255)",
+                s
+            ),
+            ParseStyleError::UnknownModifier(s) => write!(f, "unknown modifier '{}'", s),
+            ParseStyleError::UnknownUnderline(s) => {
+                write!(f, "unknown underline '{}' (expected a shape or a color)", s)
+            }
+            ParseStyleError::UnknownWord(s) => write!(f, "unknown style word '{}'", s),
+            ParseStyleError::MissingBackground => write!(f, "expected a color after 'on'"),
+        }
+    }
+}
+
+impl std::error::Error for ParseStyleError {}
+
+impl Color {
+    /// Helper to return the name of a named color.
+    fn name(self) -> Option<&'static str> {
+        let name = match self {
+            Color::Reset => "reset",
+            Color::Black => "black",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+            Color::White => "white",
+            Color::BrightBlack => "bright_black",
+            Color::BrightRed => "bright_red",
+            Color::BrightGreen => "bright_green",
+            Color::BrightYellow => "bright_yellow",
+            Color::BrightBlue => "bright_blue",
+            Color::BrightMagenta => "bright_magenta",
+            Color::BrightCyan => "bright_cyan",
+            Color::BrightWhite => "bright_white",
+            Color::Indexed(_) | Color::Rgb(..) => return None,
+        };
+        Some(name)
+    }
+}
+
+/// Formats named colors as `bright_red`, indexed colors as `208` and RGB
+/// colors as `#rrggbb`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Indexed(i) => write!(f, "{}", i),
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            named => f.write_str(named.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// Parses a color name (`red`, `bright-blue`, `gray`), a hex code (`#ff8800`,
+/// `#f80`), `rgb(255, 136, 0)` or a 256-color index (`208`).
+impl FromStr for Color {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseStyleError::Empty);
+        }
+
+        if let Some(hex) = s.strip_prefix('#') {
+            let invalid = || ParseStyleError::InvalidHex(s.to_string());
+            if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            return match hex.len() {
+                6 => Color::from_hex(hex).ok_or_else(invalid),
+                3 => {
+                    let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).map(|d| d * 17);
+                    match (digit(0), digit(1), digit(2)) {
+                        (Ok(r), Ok(g), Ok(b)) => Ok(Color::Rgb(r, g, b)),
+                        _ => Err(invalid()),
+                    }
+                }
+                _ => Err(invalid()),
+            };
+        }
+
+        let lower = s.to_ascii_lowercase();
+        if let Some(args) = lower.strip_prefix("rgb(") {
+            let invalid = || ParseStyleError::InvalidRgb(s.to_string());
+            let args = args.strip_suffix(')').ok_or_else(invalid)?;
+            let values: Vec<u8> = args
+                .split(',')
+                .map(|v| v.trim().parse::<u8>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| invalid())?;
+            return match values[..] {
+                [r, g, b] => Ok(Color::Rgb(r, g, b)),
+                _ => Err(invalid()),
+            };
+        }
+
+        if let Ok(index) = s.parse::<u8>() {
+            return Ok(Color::Indexed(index));
+        }
+
+        let color = match lower.replace('-', "_").as_str() {
+            "reset" => Color::Reset,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "white" => Color::White,
+            "bright_black" | "gray" | "grey" => Color::BrightBlack,
+            "bright_red" => Color::BrightRed,
+            "bright_green" => Color::BrightGreen,
+            "bright_yellow" => Color::BrightYellow,
+            "bright_blue" => Color::BrightBlue,
+            "bright_magenta" => Color::BrightMagenta,
+            "bright_cyan" => Color::BrightCyan,
+            "bright_white" => Color::BrightWhite,
+            _ => return Err(ParseStyleError::UnknownColor(s.to_string())),
+        };
+        Ok(color)
+    }
+}
+
+impl Modifier {
+    /// Every modifier with its name, in display order.
+    const NAMES: [(Modifier, &'static str); 10] = [
+        (Modifier::BOLD, "bold"),
+        (Modifier::DIM, "dim"),
+        (Modifier::ITALIC, "italic"),
+        (Modifier::UNDERLINE, "underline"),
+        (Modifier::SLOW_BLINK, "blink"),
+        (Modifier::RAPID_BLINK, "rapid_blink"),
+        (Modifier::REVERSED, "reversed"),
+        (Modifier::HIDDEN, "hidden"),
+        (Modifier::STRIKETHROUGH, "strikethrough"),
+        (Modifier::OVERLINE, "overline"),
+    ];
+
+    /// Returns `true` if no modifier is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Helper to iterate over the names of the modifiers in the set.
+    fn names(self) -> impl Iterator<Item = &'static str> {
+        Self::NAMES
+            .into_iter()
+            .filter(move |(m, _)| self.contains(*m))
+            .map(|(_, name)| name)
+    }
+}
+
+/// Formats the modifiers as `bold|italic`, or `none` for an empty set.
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("none");
+        }
+        for (i, name) in self.names().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses modifier names separated by `|`, like `bold|italic`. `none`
+/// is the empty set.
+impl FromStr for Modifier {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseStyleError::Empty);
+        }
+        if s == "none" {
+            return Ok(Modifier::empty());
+        }
+
+        let mut modifiers = Modifier::empty();
+        for name in s.split('|').map(str::trim) {
+            let normalized = name.to_ascii_lowercase().replace('-', "_");
+            let (modifier, _) = Self::NAMES
+                .into_iter()
+                .find(|(_, n)| *n == normalized)
+                .ok_or_else(|| ParseStyleError::UnknownModifier(name.to_string()))?;
+            modifiers.insert(modifier);
+        }
+        Ok(modifiers)
+    }
+}
+
+impl UnderlineStyle {
+    /// Helper to return the name of the shape.
+    fn name(self) -> &'static str {
+        match self {
+            UnderlineStyle::Single => "single",
+            UnderlineStyle::Double => "double",
+            UnderlineStyle::Curly => "curly",
+            UnderlineStyle::Dotted => "dotted",
+            UnderlineStyle::Dashed => "dashed",
+        }
+    }
+
+    /// Helper to parse the name of a shape.
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            UnderlineStyle::Single,
+            UnderlineStyle::Double,
+            UnderlineStyle::Curly,
+            UnderlineStyle::Dotted,
+            UnderlineStyle::Dashed,
+        ]
+        .into_iter()
+        .find(|u| u.name() == name)
+    }
+}
+
+/// Formats the style as space-separated words: modifiers, the foreground
+/// color, `on` and the background color, the underline shape and color as
+/// `underline:curly` and `underline:<color>`, then removed modifiers
+/// prefixed with `-`. The default style is `default`.
+///
+/// Styles built with the builder methods parse back to the same value.
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words: Vec<String> = Vec::new();
+
+        for name in self.modifiers.names() {
+            // The shape replaces the plain `underline` word.
+            if name == "underline" && self.underline_style.is_some() {
+                continue;
+            }
+            words.push(name.to_string());
+        }
+        if let Some(fg) = self.foreground {
+            words.push(fg.to_string());
+        }
+        if let Some(bg) = self.background {
+            words.push(format!("on {}", bg));
+        }
+        if let Some(shape) = self.underline_style {
+            words.push(format!("underline:{}", shape.name()));
+        }
+        if let Some(color) = self.underline_color {
+            words.push(format!("underline:{}", color));
+        }
+        for name in self.sub_modifiers.names() {
+            words.push(format!("-{}", name));
+        }
+
+        if words.is_empty() {
+            f.write_str("default")
+        } else {
+            f.write_str(&words.join(" "))
+        }
+    }
+}
+
+/// Parses a style like `bold italic yellow on blue`.
+///
+/// Words are modifiers (`bold`, or `bold|italic`), a foreground color,
+/// `on` followed by a background color, `underline:<shape>` (which also
+/// underlines) or `underline:<color>`, and `-<modifier>` to remove a
+/// modifier when patching. `default` is the empty style. Later words win.
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = split_words(s);
+        if words.is_empty() {
+            return Err(ParseStyleError::Empty);
+        }
+
+        let mut style = Style::default();
+        let mut words = words.into_iter();
+        while let Some(word) = words.next() {
+            let lower = word.to_ascii_lowercase();
+            if lower == "default" {
+                continue;
+            }
+            if lower == "on" {
+                let bg = words.next().ok_or(ParseStyleError::MissingBackground)?;
+                style = style.bg(bg.parse()?);
+            } else if let Some(value) = lower.strip_prefix("underline:") {
+                if let Some(shape) = UnderlineStyle::from_name(value) {
+                    style = style.underline_style(shape);
+                } else {
+                    let color = value
+                        .parse()
+                        .map_err(|_| ParseStyleError::UnknownUnderline(value.to_string()))?;
+                    style = style.underline_color(color);
+                }
+            } else if let Some(name) = word.strip_prefix('-') {
+                style = style.remove_modifier(name.parse()?);
+            } else if let Ok(modifier) = word.parse::<Modifier>() {
+                style = style.modifier(modifier);
+            } else if let Ok(color) = word.parse::<Color>() {
+                style = style.fg(color);
+            } else if word.starts_with('#') || lower.starts_with("rgb(") {
+                // Report the precise color error rather than a generic one.
+                return Err(word.parse::<Color>().unwrap_err());
+            } else {
+                return Err(ParseStyleError::UnknownWord(word.to_string()));
+            }
+        }
+        Ok(style)
+    }
+}
+
+/// Helper to split a style string on whitespace, keeping `rgb(r, g, b)`
+/// in one word.
+fn split_words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(begin) = start.take() {
+                    words.push(&s[begin..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(begin) = start {
+        words.push(&s[begin..]);
+    }
+    words
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +896,232 @@ mod tests {
         // Assuming: Reset; FG; BG; Modifiers
         assert_eq!(style.to_ansi(), "\x1b[0;31;44;1m");
     }
+
+    #[test]
+    fn test_style_to_ansi_extended_modifiers() {
+        let style = Style::new()
+            .modifier(Modifier::STRIKETHROUGH | Modifier::SLOW_BLINK | Modifier::OVERLINE)
+            .modifier(Modifier::HIDDEN | Modifier::RAPID_BLINK);
+        assert_eq!(style.to_ansi(), "\x1b[0;5;6;8;9;53m");
+    }
+
+    #[test]
+    fn test_style_to_ansi_underline_fallbacks() {
+        let style = Style::new()
+            .underline_style(UnderlineStyle::Curly)
+            .underline_color(Color::Rgb(255, 0, 0));
+
+        assert_eq!(style.to_ansi(), "\x1b[0;4:3;58;2;255;0;0m");
+        assert_eq!(style.to_ansi_with(Capabilities::basic()), "\x1b[0;4m");
+
+        let no_color = Capabilities {
+            underline_color: false,
+            ..Capabilities::all()
+        };
+        assert_eq!(style.to_ansi_with(no_color), "\x1b[0;4:3m");
+
+        // The underline color alone does not underline.
+        let colored = Style::new().underline_color(Color::Red);
+        assert_eq!(colored.to_ansi(), "\x1b[0m");
+        assert_eq!(
+            colored.modifier(Modifier::UNDERLINE).to_ansi(),
+            "\x1b[0;4;58;5;1m"
+        );
+    }
+
+    #[test]
+    fn test_capabilities_detection() {
+        assert_eq!(
+            Capabilities::detect_from(Some("xterm-kitty"), None, None),
+            Capabilities::all()
+        );
+        assert_eq!(
+            Capabilities::detect_from(Some("xterm-256color"), Some("WezTerm"), None),
+            Capabilities::all()
+        );
+        assert_eq!(
+            Capabilities::detect_from(Some("xterm-256color"), None, Some("7600")),
+            Capabilities {
+                left_right_margins: false,
+                ..Capabilities::all()
+            }
+        );
+        assert!(!Capabilities::detect_from(Some("alacritty"), None, None).left_right_margins);
+        assert_eq!(
+            Capabilities::detect_from(Some("xterm-256color"), None, None),
+            Capabilities::basic()
+        );
+        assert_eq!(
+            Capabilities::detect_from(None, None, None),
+            Capabilities::basic()
+        );
+    }
+
+    #[test]
+    fn test_color_from_str() {
+        assert_eq!("red".parse(), Ok(Color::Red));
+        assert_eq!("Bright-Blue".parse(), Ok(Color::BrightBlue));
+        assert_eq!("grey".parse(), Ok(Color::BrightBlack));
+        assert_eq!("#FF8800".parse(), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!("#f80".parse(), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!("rgb(1, 2, 3)".parse(), Ok(Color::Rgb(1, 2, 3)));
+        assert_eq!("208".parse(), Ok(Color::Indexed(208)));
+
+        assert_eq!("".parse::<Color>(), Err(ParseStyleError::Empty));
+        assert_eq!(
+            "#12345".parse::<Color>(),
+            Err(ParseStyleError::InvalidHex("#12345".to_string()))
+        );
+        assert_eq!(
+            "rgb(1, 2)".parse::<Color>(),
+            Err(ParseStyleError::InvalidRgb("rgb(1, 2)".to_string()))
+        );
+        assert_eq!(
+            "256".parse::<Color>(),
+            Err(ParseStyleError::UnknownColor("256".to_string()))
+        );
+        assert_eq!(
+            "purple".parse::<Color>().unwrap_err().to_string(),
+            "unknown color 'purple' (expected a name, #rrggbb, #rgb, rgb(r, g, b) or 0-255)"
+        );
+    }
+
+    #[test]
+    fn test_color_display_round_trip() {
+        let mut colors = vec![
+            Color::Reset,
+            Color::Black,
+            Color::BrightWhite,
+            Color::Rgb(0, 10, 255),
+        ];
+        colors.extend((0..=255).map(Color::Indexed));
+
+        for color in colors {
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+        assert_eq!(Color::Rgb(0, 10, 255).to_string(), "#000aff");
+        assert_eq!(Color::BrightRed.to_string(), "bright_red");
+    }
+
+    #[test]
+    fn test_modifier_from_str_and_display() {
+        assert_eq!("bold|italic".parse(), Ok(Modifier::BOLD | Modifier::ITALIC));
+        assert_eq!("none".parse(), Ok(Modifier::empty()));
+        assert_eq!(
+            "bold|shiny".parse::<Modifier>(),
+            Err(ParseStyleError::UnknownModifier("shiny".to_string()))
+        );
+
+        let all = Modifier::NAMES
+            .into_iter()
+            .fold(Modifier::empty(), |acc, (m, _)| acc | m);
+        assert_eq!(all.to_string().parse(), Ok(all));
+        assert_eq!(
+            (Modifier::ITALIC | Modifier::BOLD).to_string(),
+            "bold|italic"
+        );
+        assert_eq!(Modifier::empty().to_string(), "none");
+    }
+
+    #[test]
+    fn test_style_from_str() {
+        assert_eq!(
+            "bold yellow on blue".parse(),
+            Ok(Style::new()
+                .modifier(Modifier::BOLD)
+                .fg(Color::Yellow)
+                .bg(Color::Blue))
+        );
+        assert_eq!(
+            "rgb(1, 2, 3) underline:curly underline:red -italic".parse(),
+            Ok(Style::new()
+                .fg(Color::Rgb(1, 2, 3))
+                .underline_style(UnderlineStyle::Curly)
+                .underline_color(Color::Red)
+                .remove_modifier(Modifier::ITALIC))
+        );
+        assert_eq!("default".parse(), Ok(Style::default()));
+
+        assert_eq!("".parse::<Style>(), Err(ParseStyleError::Empty));
+        assert_eq!(
+            "bold on".parse::<Style>(),
+            Err(ParseStyleError::MissingBackground)
+        );
+        assert_eq!(
+            "bold loud".parse::<Style>(),
+            Err(ParseStyleError::UnknownWord("loud".to_string()))
+        );
+        assert_eq!(
+            "#12".parse::<Style>(),
+            Err(ParseStyleError::InvalidHex("#12".to_string()))
+        );
+        assert_eq!(
+            "underline:wavy".parse::<Style>(),
+            Err(ParseStyleError::UnknownUnderline("wavy".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_style_display_round_trip() {
+        let styles = [
+            Style::default(),
+            Style::new().fg(Color::Red),
+            Style::new().bg(Color::Indexed(17)),
+            Style::new()
+                .fg(Color::Rgb(1, 2, 3))
+                .bg(Color::Reset)
+                .modifier(Modifier::BOLD | Modifier::OVERLINE)
+                .remove_modifier(Modifier::DIM),
+            Style::new()
+                .underline_style(UnderlineStyle::Dashed)
+                .underline_color(Color::Rgb(9, 9, 9)),
+            Style::new()
+                .underline_style(UnderlineStyle::Single)
+                .remove_modifier(Modifier::UNDERLINE),
+            Style::new().underline_color(Color::Blue),
+        ];
+
+        for style in styles {
+            assert_eq!(style.to_string().parse(), Ok(style), "{}", style);
+        }
+        assert_eq!(
+            Style::new()
+                .fg(Color::Yellow)
+                .bg(Color::Blue)
+                .modifier(Modifier::BOLD)
+                .to_string(),
+            "bold yellow on blue"
+        );
+    }
+
+    #[test]
+    fn test_style_patch() {
+        let base = Style::new()
+            .fg(Color::Red)
+            .bg(Color::Blue)
+            .modifier(Modifier::BOLD | Modifier::UNDERLINE);
+
+        // Unset fields are inherited.
+        assert_eq!(base.patch(Style::default()), base);
+
+        let patched = base.patch(
+            Style::new()
+                .fg(Color::Green)
+                .modifier(Modifier::ITALIC)
+                .remove_modifier(Modifier::BOLD),
+        );
+        assert_eq!(patched.foreground, Some(Color::Green));
+        assert_eq!(patched.background, Some(Color::Blue));
+        assert!(
+            patched
+                .modifiers
+                .contains(Modifier::ITALIC | Modifier::UNDERLINE)
+        );
+        assert!(!patched.modifiers.contains(Modifier::BOLD));
+
+        // A removal survives further patching until re-added.
+        let chained = Style::default().patch(patched).patch(base);
+        assert!(chained.modifiers.contains(Modifier::BOLD));
+        assert_eq!(chained.sub_modifiers, Modifier::empty());
+    }
 }
@@ -309,13 +309,22 @@ impl Terminal {
         Ok(())
     }
 
+    /// Enables mouse reporting for clicks and scrolling.
     pub fn enable_mouse_capture(&self) -> io::Result<()> {
         self.write(b"\x1b[?1000h")?;
         Ok(())
     }
 
+    /// Enables reporting of every pointer move (`?1003`), which allows hover
+    /// tracking. Each move is an event, so this is opt-in.
+    pub fn enable_mouse_motion(&self) -> io::Result<()> {
+        self.write(b"\x1b[?1003h")?;
+        Ok(())
+    }
+
+    /// Disables all mouse reporting.
     pub fn disable_mouse_capture(&self) -> io::Result<()> {
-        self.write(b"\x1b[?1000l")?;
+        self.write(b"\x1b[?1003l\x1b[?1000l")?;
         Ok(())
     }
 }
@@ -327,6 +336,8 @@ impl Drop for Terminal {
     fn drop(&mut self) {
         let _ = self.disable_mouse_capture();
         let _ = self.exit_alternate_buffer();
+        // Restore the user's cursor shape, which frames may have changed.
+        let _ = self.write(b"\x1b[0 q");
         let _ = self.show_cursor();
 
         if let Some(termios) = self.original_termios
@@ -378,7 +389,7 @@ pub(crate) mod mocks {
         fn open_tty(&self) -> io::Result<RawFd> {
             self.push_log("open_tty");
             if self.fail_open {
-                return Err(io::Error::new(io::ErrorKind::Other, "Mock Open Failed"));
+                return Err(io::Error::other("Mock Open Failed"));
             }
             Ok(100)
         }
@@ -391,10 +402,7 @@ pub(crate) mod mocks {
         fn enable_raw(&self, fd: RawFd) -> io::Result<libc::termios> {
             self.push_log(&format!("enable_raw({})", fd));
             if self.fail_enable_raw {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "Mock Enable Raw Failed",
-                ));
+                return Err(io::Error::other("Mock Enable Raw Failed"));
             }
             // Return empty termios
             Ok(unsafe { std::mem::zeroed() })
@@ -485,12 +493,13 @@ mod tests {
         assert_eq!(log[5], "get_window_size(100)");
         assert_eq!(log[6], "write(100, \"foo\")");
         assert_eq!(log[7], "read(100)");
-        assert_eq!(log[8], "write(100, \"\x1b[?1000l\")");
+        assert_eq!(log[8], "write(100, \"\x1b[?1003l\x1b[?1000l\")");
         assert_eq!(log[9], "write(100, \"\x1b[?1049l\")");
-        assert_eq!(log[10], "write(100, \"\x1b[?25h\")");
-        assert_eq!(log[11], "disable_raw(100)");
-        assert_eq!(log[12], "close_tty");
-        assert_eq!(log.len(), 13);
+        assert_eq!(log[10], "write(100, \"\x1b[0 q\")");
+        assert_eq!(log[11], "write(100, \"\x1b[?25h\")");
+        assert_eq!(log[12], "disable_raw(100)");
+        assert_eq!(log[13], "close_tty");
+        assert_eq!(log.len(), 14);
     }
 
     #[test]
@@ -0,0 +1,349 @@
+//! The `text` module provides a rich-text model for mixed styling.
+//!
+//! * [`Span`]: A string with a single [`Style`].
+//! * [`Line`]: A sequence of spans drawn on one row, with an optional [`Alignment`].
+//! * [`StyledText`]: A sequence of lines.
+//!
+//! All types measure their width in terminal columns rather than bytes or
+//! chars, so wide characters (CJK, emoji) and zero-width marks are accounted
+//! for. Plain strings convert into each of them via `From`.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Color, Line, Span, Style};
+//!
+//! let line = Line::new(vec![
+//!     Span::styled("Error: ", Style::new().fg(Color::Red)),
+//!     Span::raw("file not found"),
+//! ]);
+//! assert_eq!(line.width(), 21);
+//! ```
+
+use crate::Style;
+
+/// Horizontal alignment of a [`Line`] within its area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Alignment {
+    /// Aligned to the left edge.
+    #[default]
+    Left,
+    /// Centered (extra column goes to the right).
+    Center,
+    /// Aligned to the right edge.
+    Right,
+}
+
+impl Alignment {
+    /// Returns the offset at which content of `content_width` columns starts
+    /// inside an area `available` columns wide.
+    pub fn offset(self, content_width: usize, available: usize) -> usize {
+        let free = available.saturating_sub(content_width);
+        match self {
+            Alignment::Left => 0,
+            Alignment::Center => free / 2,
+            Alignment::Right => free,
+        }
+    }
+}
+
+/// A string of text with a single style.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    /// The text of the span. Should not contain newlines.
+    pub content: String,
+    /// The style applied to the whole span.
+    pub style: Style,
+}
+
+impl Span {
+    /// Creates an unstyled span.
+    pub fn raw<S: Into<String>>(content: S) -> Self {
+        Self {
+            content: content.into(),
+            style: Style::default(),
+        }
+    }
+
+    /// Creates a span with the given style.
+    pub fn styled<S: Into<String>>(content: S, style: Style) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+
+    /// Sets the style of the span.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns the width of the span in terminal columns.
+    pub fn width(&self) -> usize {
+        str_width(&self.content)
+    }
+}
+
+impl From<&str> for Span {
+    fn from(s: &str) -> Self {
+        Span::raw(s)
+    }
+}
+
+impl From<String> for Span {
+    fn from(s: String) -> Self {
+        Span::raw(s)
+    }
+}
+
+/// A single line of styled text, made of [`Span`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Line {
+    /// The spans of the line, drawn left to right.
+    pub spans: Vec<Span>,
+    /// The alignment of the line.
+    ///
+    /// `None` defers to the container (e.g. [`Text::alignment`](crate::widgets::Text::alignment)).
+    pub alignment: Option<Alignment>,
+}
+
+impl Line {
+    /// Creates a line from a list of spans.
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self {
+            spans,
+            alignment: None,
+        }
+    }
+
+    /// Creates a line made of a single unstyled span.
+    pub fn raw<S: Into<String>>(content: S) -> Self {
+        Self::new(vec![Span::raw(content)])
+    }
+
+    /// Creates a line made of a single styled span.
+    pub fn styled<S: Into<String>>(content: S, style: Style) -> Self {
+        Self::new(vec![Span::styled(content, style)])
+    }
+
+    /// Sets the alignment of the line.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Appends a span to the line.
+    pub fn push_span<S: Into<Span>>(&mut self, span: S) {
+        self.spans.push(span.into());
+    }
+
+    /// Returns the width of the line in terminal columns.
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+
+    /// Returns `true` if the line has no visible content.
+    pub fn is_empty(&self) -> bool {
+        self.spans.iter().all(|s| s.content.is_empty())
+    }
+}
+
+impl From<&str> for Line {
+    fn from(s: &str) -> Self {
+        Line::raw(s)
+    }
+}
+
+impl From<String> for Line {
+    fn from(s: String) -> Self {
+        Line::raw(s)
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Line::new(vec![span])
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Line::new(spans)
+    }
+}
+
+/// Multi-line styled text, made of [`Line`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct StyledText {
+    /// The lines of the text, drawn top to bottom.
+    pub lines: Vec<Line>,
+}
+
+impl StyledText {
+    /// Creates a text from a list of lines.
+    pub fn new(lines: Vec<Line>) -> Self {
+        Self { lines }
+    }
+
+    /// Creates unstyled text, splitting the content on newlines.
+    pub fn raw(content: &str) -> Self {
+        Self::new(content.lines().map(Line::raw).collect())
+    }
+
+    /// Creates text with a single style, splitting the content on newlines.
+    pub fn styled(content: &str, style: Style) -> Self {
+        Self::new(
+            content
+                .lines()
+                .map(|line| Line::styled(line, style))
+                .collect(),
+        )
+    }
+
+    /// Appends a line to the text.
+    pub fn push_line<L: Into<Line>>(&mut self, line: L) {
+        self.lines.push(line.into());
+    }
+
+    /// Returns the width of the widest line in terminal columns.
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(Line::width).max().unwrap_or(0)
+    }
+
+    /// Returns the number of lines.
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+impl From<&str> for StyledText {
+    fn from(s: &str) -> Self {
+        StyledText::raw(s)
+    }
+}
+
+impl From<&String> for StyledText {
+    fn from(s: &String) -> Self {
+        StyledText::raw(s)
+    }
+}
+
+impl From<String> for StyledText {
+    fn from(s: String) -> Self {
+        StyledText::raw(&s)
+    }
+}
+
+impl From<Span> for StyledText {
+    fn from(span: Span) -> Self {
+        StyledText::new(vec![Line::from(span)])
+    }
+}
+
+impl From<Line> for StyledText {
+    fn from(line: Line) -> Self {
+        StyledText::new(vec![line])
+    }
+}
+
+impl From<Vec<Line>> for StyledText {
+    fn from(lines: Vec<Line>) -> Self {
+        StyledText::new(lines)
+    }
+}
+
+/// Returns the number of terminal columns a character occupies.
+///
+/// Control characters and combining marks take no space, East Asian wide
+/// characters and most emoji take two columns, everything else takes one.
+/// This is a compact approximation of Unicode's East Asian Width property.
+pub fn char_width(c: char) -> usize {
+    let cp = c as u32;
+    match cp {
+        0..=0x1F | 0x7F..=0x9F => 0,
+        // Combining marks, zero-width spaces/joiners and variation selectors.
+        0x0300..=0x036F
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD
+        | 0x0610..=0x061A
+        | 0x064B..=0x065F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        // Wide ranges: Hangul Jamo, CJK, Hangul syllables, full-width forms, emoji.
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the number of terminal columns a string occupies.
+pub fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Modifier};
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('漢'), 2);
+        assert_eq!(char_width('🚀'), 2);
+        assert_eq!(char_width('\u{0301}'), 0); // combining acute accent
+        assert_eq!(char_width('\n'), 0);
+        assert_eq!(str_width("日本 ok"), 7);
+    }
+
+    #[test]
+    fn test_line_width_and_conversions() {
+        let line = Line::new(vec![
+            Span::styled("ab", Style::new().fg(Color::Red)),
+            Span::raw("漢"),
+        ]);
+        assert_eq!(line.width(), 4);
+        assert_eq!(Line::from("hi"), Line::raw("hi"));
+        assert!(Line::default().is_empty());
+        assert_eq!(
+            Line::from(Span::styled("x", Style::new().modifier(Modifier::BOLD))).spans[0]
+                .style
+                .modifiers,
+            Modifier::BOLD
+        );
+    }
+
+    #[test]
+    fn test_styled_text_from_str_splits_lines() {
+        let text = StyledText::from("one\nthree\n漢字");
+        assert_eq!(text.height(), 3);
+        assert_eq!(text.width(), 5);
+        assert_eq!(text.lines[1], Line::raw("three"));
+    }
+
+    #[test]
+    fn test_alignment_offset() {
+        assert_eq!(Alignment::Left.offset(4, 10), 0);
+        assert_eq!(Alignment::Center.offset(4, 10), 3);
+        assert_eq!(Alignment::Right.offset(4, 10), 6);
+        assert_eq!(Alignment::Right.offset(20, 10), 0);
+    }
+}
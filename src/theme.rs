@@ -0,0 +1,478 @@
+//! The `theme` module maps semantic roles to styles.
+//!
+//! Instead of hardcoding colors, widgets look up the [`Style`] of a [`Role`]
+//! (border, title, selection, ...) in the [`Theme`] of the [`Frame`](crate::Frame)
+//! they are drawn on, then patch their own style on top. Switching the theme
+//! restyles the whole application.
+//!
+//! [`Theme::default`] is empty, so widgets look the same as without a theme.
+//! [`Theme::dark`] and [`Theme::light`] are ready-made presets.
+//!
+//! # File format
+//! Themes can be loaded from a small INI-like format:
+//!
+//! ```text
+//! # Start from a preset (optional, must come first).
+//! base = "dark"
+//!
+//! # Named colors, usable in the sections below.
+//! [palette]
+//! accent = "#5f87af"
+//!
+//! # One section per role.
+//! [border]
+//! fg = "accent"
+//!
+//! [title]
+//! fg = "bright_yellow"
+//! bg = "#202020"
+//! modifiers = "bold italic"
+//!
+//! [muted]
+//! modifiers = "dim -bold"
+//! ```
+//!
+//! Colors accept anything [`Color`]'s `FromStr` implementation does: names,
+//! hex codes, `rgb(r, g, b)` and indexes. A modifier prefixed with `-` is removed
+//! when the style is patched. Lines starting with `#` or `;` are comments.
+//!
+//! # Example
+//! ```
+//! use phosphor::{Color, Role, Theme};
+//!
+//! let theme = Theme::parse("[error]\nfg = \"#ff0000\"\n").unwrap();
+//! assert_eq!(theme.style(Role::Error).foreground, Some(Color::Rgb(255, 0, 0)));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{Color, Modifier, Style};
+
+/// A semantic role that a widget can style itself with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Regular text.
+    Text,
+    /// Secondary text, like hints and placeholders.
+    Muted,
+    /// Borders of blocks and panes.
+    Border,
+    /// Borders and indicators of the focused component.
+    Focus,
+    /// Titles of blocks.
+    Title,
+    /// The selected item of a list or table.
+    Selection,
+    /// Emphasized content, like the filled part of a gauge.
+    Highlight,
+    /// Error messages.
+    Error,
+    /// Warning messages.
+    Warning,
+    /// Success messages.
+    Success,
+    /// Informational messages.
+    Info,
+}
+
+impl Role {
+    /// Every role, in declaration order.
+    pub const ALL: [Role; 11] = [
+        Role::Text,
+        Role::Muted,
+        Role::Border,
+        Role::Focus,
+        Role::Title,
+        Role::Selection,
+        Role::Highlight,
+        Role::Error,
+        Role::Warning,
+        Role::Success,
+        Role::Info,
+    ];
+
+    /// Returns the name of the role, as used in theme files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Text => "text",
+            Role::Muted => "muted",
+            Role::Border => "border",
+            Role::Focus => "focus",
+            Role::Title => "title",
+            Role::Selection => "selection",
+            Role::Highlight => "highlight",
+            Role::Error => "error",
+            Role::Warning => "warning",
+            Role::Success => "success",
+            Role::Info => "info",
+        }
+    }
+
+    /// Returns the role with the given name, if any.
+    pub fn from_name(name: &str) -> Option<Role> {
+        Role::ALL.into_iter().find(|r| r.name() == name)
+    }
+}
+
+/// A set of styles, one per [`Role`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Theme {
+    styles: [Style; Role::ALL.len()],
+}
+
+impl Theme {
+    /// Creates an empty theme, where every role has the default style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the style of a role.
+    pub fn style(&self, role: Role) -> Style {
+        self.styles[role as usize]
+    }
+
+    /// Sets the style of a role.
+    pub fn set(&mut self, role: Role, style: Style) {
+        self.styles[role as usize] = style;
+    }
+
+    /// Sets the style of a role.
+    pub fn with(mut self, role: Role, style: Style) -> Self {
+        self.set(role, style);
+        self
+    }
+
+    /// A preset for dark terminal backgrounds.
+    pub fn dark() -> Self {
+        let blue = Color::Rgb(95, 135, 215);
+        let yellow = Color::Rgb(230, 180, 80);
+        Self::new()
+            .with(Role::Text, Style::new().fg(Color::Rgb(220, 220, 220)))
+            .with(Role::Muted, Style::new().fg(Color::Rgb(128, 128, 128)))
+            .with(Role::Border, Style::new().fg(Color::Rgb(88, 110, 140)))
+            .with(Role::Focus, Style::new().fg(blue))
+            .with(
+                Role::Title,
+                Style::new().fg(yellow).modifier(Modifier::BOLD),
+            )
+            .with(
+                Role::Selection,
+                Style::new()
+                    .fg(Color::Rgb(255, 255, 255))
+                    .bg(Color::Rgb(50, 70, 110)),
+            )
+            .with(
+                Role::Highlight,
+                Style::new().fg(blue).modifier(Modifier::BOLD),
+            )
+            .with(Role::Error, Style::new().fg(Color::Rgb(235, 90, 90)))
+            .with(Role::Warning, Style::new().fg(yellow))
+            .with(Role::Success, Style::new().fg(Color::Rgb(120, 200, 120)))
+            .with(Role::Info, Style::new().fg(blue))
+    }
+
+    /// A preset for light terminal backgrounds.
+    pub fn light() -> Self {
+        let blue = Color::Rgb(30, 90, 200);
+        Self::new()
+            .with(Role::Text, Style::new().fg(Color::Rgb(40, 40, 40)))
+            .with(Role::Muted, Style::new().fg(Color::Rgb(120, 120, 120)))
+            .with(Role::Border, Style::new().fg(Color::Rgb(150, 160, 175)))
+            .with(Role::Focus, Style::new().fg(blue))
+            .with(Role::Title, Style::new().fg(blue).modifier(Modifier::BOLD))
+            .with(
+                Role::Selection,
+                Style::new()
+                    .fg(Color::Rgb(20, 20, 20))
+                    .bg(Color::Rgb(200, 220, 250)),
+            )
+            .with(
+                Role::Highlight,
+                Style::new().fg(blue).modifier(Modifier::BOLD),
+            )
+            .with(Role::Error, Style::new().fg(Color::Rgb(190, 30, 30)))
+            .with(Role::Warning, Style::new().fg(Color::Rgb(170, 110, 0)))
+            .with(Role::Success, Style::new().fg(Color::Rgb(30, 130, 50)))
+            .with(Role::Info, Style::new().fg(blue))
+    }
+
+    /// Returns the preset with the given name: `default`, `dark` or `light`.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            _ => None,
+        }
+    }
+
+    /// Parses a theme from the text format described in the [module docs](self).
+    ///
+    /// Roles missing from the input keep the style of the `base` preset, or
+    /// the default style if there is none.
+    ///
+    /// # Errors
+    /// Returns a [`ThemeError`] pointing at the offending line.
+    pub fn parse(input: &str) -> Result<Self, ThemeError> {
+        let mut theme = Theme::default();
+        let mut palette: HashMap<String, Color> = HashMap::new();
+        let mut section: Option<Section> = None;
+
+        for (index, raw) in input.lines().enumerate() {
+            let line = index + 1;
+            let error = |kind| Err(ThemeError { line, kind });
+
+            let text = raw.trim();
+            if text.is_empty() || text.starts_with('#') || text.starts_with(';') {
+                continue;
+            }
+
+            if let Some(name) = text.strip_prefix('[') {
+                let Some(name) = name.strip_suffix(']') else {
+                    return error(ThemeErrorKind::UnterminatedSection);
+                };
+                let name = name.trim();
+                section = match name {
+                    "palette" => Some(Section::Palette),
+                    _ => match Role::from_name(name) {
+                        Some(role) => Some(Section::Role(role)),
+                        None => return error(ThemeErrorKind::UnknownSection(name.to_string())),
+                    },
+                };
+                continue;
+            }
+
+            let Some((key, value)) = text.split_once('=') else {
+                return error(ThemeErrorKind::MissingValue);
+            };
+            let key = key.trim();
+            let value = unquote(value.trim());
+
+            match section {
+                None if key == "base" => match Theme::preset(value) {
+                    Some(preset) => theme = preset,
+                    None => return error(ThemeErrorKind::UnknownPreset(value.to_string())),
+                },
+                None => return error(ThemeErrorKind::UnknownKey(key.to_string())),
+                Some(Section::Palette) => match value.parse::<Color>() {
+                    Ok(color) => {
+                        palette.insert(key.to_string(), color);
+                    }
+                    Err(_) => return error(ThemeErrorKind::InvalidColor(value.to_string())),
+                },
+                Some(Section::Role(role)) => {
+                    let color = || {
+                        palette
+                            .get(value)
+                            .copied()
+                            .or_else(|| value.parse().ok())
+                            .ok_or_else(|| ThemeError {
+                                line,
+                                kind: ThemeErrorKind::InvalidColor(value.to_string()),
+                            })
+                    };
+                    let style = &mut theme.styles[role as usize];
+                    match key {
+                        "fg" | "foreground" => style.foreground = Some(color()?),
+                        "bg" | "background" => style.background = Some(color()?),
+                        "underline_color" => style.underline_color = Some(color()?),
+                        "modifiers" => {
+                            style.modifiers = Modifier::empty();
+                            style.sub_modifiers = Modifier::empty();
+                            for word in value.split([' ', ',']).filter(|w| !w.is_empty()) {
+                                let (name, remove) = match word.strip_prefix('-') {
+                                    Some(name) => (name, true),
+                                    None => (word, false),
+                                };
+                                let Ok(modifier) = name.parse::<Modifier>() else {
+                                    return error(ThemeErrorKind::UnknownModifier(
+                                        word.to_string(),
+                                    ));
+                                };
+                                *style = if remove {
+                                    style.remove_modifier(modifier)
+                                } else {
+                                    style.modifier(modifier)
+                                };
+                            }
+                        }
+                        _ => return error(ThemeErrorKind::UnknownKey(key.to_string())),
+                    }
+                }
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Reads and parses a theme file.
+    ///
+    /// Parse errors are reported as [`io::ErrorKind::InvalidData`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let input = std::fs::read_to_string(path)?;
+        Theme::parse(&input).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Theme::parse(s)
+    }
+}
+
+/// The section of a theme file being parsed.
+#[derive(Clone, Copy)]
+enum Section {
+    Palette,
+    Role(Role),
+}
+
+/// Helper to strip matching double quotes around a value.
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// The reason a theme failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeErrorKind {
+    /// A `[` section header was never closed by a `]`.
+    UnterminatedSection,
+    /// A section that is neither `palette` nor a role name.
+    UnknownSection(String),
+    /// A line that is not a section header nor a `key = value` pair.
+    MissingValue,
+    /// A key that is not valid in its section.
+    UnknownKey(String),
+    /// A value that is not a color, a palette entry or a 256-color index.
+    InvalidColor(String),
+    /// A word in `modifiers` that is not a modifier.
+    UnknownModifier(String),
+    /// A `base` that is not a preset name.
+    UnknownPreset(String),
+}
+
+/// An error produced by [`Theme::parse`], with the line where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeError {
+    /// The line number (starting at 1) where the error was detected.
+    pub line: usize,
+    /// What went wrong.
+    pub kind: ThemeErrorKind,
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ThemeErrorKind::UnterminatedSection => write!(f, "unterminated section header")?,
+            ThemeErrorKind::UnknownSection(s) => write!(f, "unknown section '{}'", s)?,
+            ThemeErrorKind::MissingValue => write!(f, "expected 'key = value'")?,
+            ThemeErrorKind::UnknownKey(k) => write!(f, "unknown key '{}'", k)?,
+            ThemeErrorKind::InvalidColor(c) => write!(f, "invalid color '{}'", c)?,
+            ThemeErrorKind::UnknownModifier(m) => write!(f, "unknown modifier '{}'", m)?,
+            ThemeErrorKind::UnknownPreset(p) => write!(f, "unknown preset '{}'", p)?,
+        }
+        write!(f, " on line {}", self.line)
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_parse_sections_and_palette() {
+        let input = r##"
+# A comment
+base = "dark"
+
+[palette]
+accent = "#5f87af"
+
+[border]
+fg = accent
+bg = "#202020"
+
+[title]
+fg = "bright_yellow"
+modifiers = "bold, italic -dim"
+"##;
+        let theme = Theme::parse(input).unwrap();
+
+        let border = theme.style(Role::Border);
+        assert_eq!(border.foreground, Some(Color::Rgb(95, 135, 175)));
+        assert_eq!(border.background, Some(Color::Rgb(32, 32, 32)));
+
+        let title = theme.style(Role::Title);
+        assert_eq!(title.foreground, Some(Color::BrightYellow));
+        assert_eq!(title.modifiers, Modifier::BOLD | Modifier::ITALIC);
+        assert_eq!(title.sub_modifiers, Modifier::DIM);
+
+        // Roles not in the file come from the base preset.
+        assert_eq!(theme.style(Role::Error), Theme::dark().style(Role::Error));
+    }
+
+    #[test]
+    fn test_theme_parse_errors() {
+        let err = |input: &str| Theme::parse(input).unwrap_err();
+
+        assert_eq!(
+            err("[border]\nfg = nope"),
+            ThemeError {
+                line: 2,
+                kind: ThemeErrorKind::InvalidColor("nope".to_string())
+            }
+        );
+        assert_eq!(
+            err("[sidebar]").kind,
+            ThemeErrorKind::UnknownSection("sidebar".to_string())
+        );
+        assert_eq!(err("[title").kind, ThemeErrorKind::UnterminatedSection);
+        assert_eq!(err("[title]\nbold").kind, ThemeErrorKind::MissingValue);
+        assert_eq!(
+            err("[title]\nmodifiers = shiny").kind,
+            ThemeErrorKind::UnknownModifier("shiny".to_string())
+        );
+        assert_eq!(
+            err("base = neon").kind,
+            ThemeErrorKind::UnknownPreset("neon".to_string())
+        );
+        assert_eq!(err("fg = red").to_string(), "unknown key 'fg' on line 1");
+    }
+
+    #[test]
+    fn test_theme_presets() {
+        assert_eq!(Theme::preset("default"), Some(Theme::default()));
+        assert_eq!(Theme::preset("light"), Some(Theme::light()));
+        assert_eq!(Theme::preset("solarized"), None);
+        assert_ne!(Theme::dark(), Theme::light());
+
+        // Presets keep text readable on their intended background.
+        let dark_text = Theme::dark().style(Role::Text).foreground.unwrap();
+        let light_text = Theme::light().style(Role::Text).foreground.unwrap();
+        assert!(dark_text.contrast_ratio(Color::Black).unwrap() >= 4.5);
+        assert!(
+            light_text
+                .contrast_ratio(Color::Rgb(255, 255, 255))
+                .unwrap()
+                >= 4.5
+        );
+    }
+
+    #[test]
+    fn test_role_names_round_trip() {
+        for role in Role::ALL {
+            assert_eq!(Role::from_name(role.name()), Some(role));
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! A container widget with optional borders and title.
 
-use crate::{Frame, Rect, Style, widgets::Widget};
+use crate::{
+    Frame, Line, Rect, Role, Size, Style,
+    widgets::{Measure, Widget, WidgetRef},
+};
 
 const P_BORDER_H: char = '\u{2500}';
 const P_BORDER_V: char = '\u{2502}';
@@ -93,8 +96,9 @@ impl std::ops::BitOr for Borders {
 }
 
 /// A container widget that can display a border and a title.
+#[derive(Debug, Clone)]
 pub struct Block {
-    title: Option<String>,
+    title: Option<Line>,
     borders: Borders,
     border_type: BorderType,
     style: Style,
@@ -122,7 +126,11 @@ impl Block {
         }
     }
 
-    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+    /// Sets the title drawn on the top border.
+    ///
+    /// Accepts a plain string or a styled [`Line`]. The line's alignment
+    /// decides where the title is placed along the border.
+    pub fn title<L: Into<Line>>(mut self, title: L) -> Self {
         self.title = Some(title.into());
         self
     }
@@ -138,12 +146,16 @@ impl Block {
     }
 
     /// Sets the style of the borders.
+    ///
+    /// It is patched over the theme's [`Role::Border`] style.
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
 
     /// Sets the style of the title.
+    ///
+    /// It is patched over the border style and the theme's [`Role::Title`] style.
     pub fn title_style(mut self, style: Style) -> Self {
         self.title_style = style;
         self
@@ -178,11 +190,11 @@ impl Block {
         let mut h = area.height;
 
         if self.borders.contains(Borders::LEFT) {
-            x += 1;
+            x = x.saturating_add(1);
             w = w.saturating_sub(1);
         }
         if self.borders.contains(Borders::TOP) {
-            y += 1;
+            y = y.saturating_add(1);
             h = h.saturating_sub(1);
         }
         if self.borders.contains(Borders::RIGHT) {
@@ -192,22 +204,69 @@ impl Block {
             h = h.saturating_sub(1);
         }
 
-        x += self.padding_x;
-        y += self.padding_y;
-        w = w.saturating_sub(self.padding_x * 2);
-        h = h.saturating_sub(self.padding_y * 2);
+        x = x.saturating_add(self.padding_x);
+        y = y.saturating_add(self.padding_y);
+        w = w.saturating_sub(self.padding_x.saturating_mul(2));
+        h = h.saturating_sub(self.padding_y.saturating_mul(2));
 
         Rect::new(x, y, w, h)
     }
+
+    /// Returns the size of a block whose [`inner`](Self::inner) area has the
+    /// given size, widened if needed so the title fits.
+    ///
+    /// This is the inverse of [`inner`](Self::inner), useful to measure a
+    /// block around some content.
+    pub fn outer_size(&self, inner: Size) -> Size {
+        let edges = |a: Borders, b: Borders| {
+            self.borders.contains(a) as u16 + self.borders.contains(b) as u16
+        };
+        let width = inner
+            .width
+            .saturating_add(edges(Borders::LEFT, Borders::RIGHT))
+            .saturating_add(self.padding_x.saturating_mul(2));
+        let height = inner
+            .height
+            .saturating_add(edges(Borders::TOP, Borders::BOTTOM))
+            .saturating_add(self.padding_y.saturating_mul(2));
+
+        // The title is padded with a space on each side and inset by two columns.
+        match &self.title {
+            Some(title) => {
+                let title_width = (title.width() + 6).min(u16::MAX as usize) as u16;
+                Size::new(width.max(title_width), height.max(1))
+            }
+            None => Size::new(width, height),
+        }
+    }
+}
+
+impl Measure for Block {
+    /// Returns the size taken by the borders, padding and title alone.
+    fn preferred_size(&self, max_width: u16) -> Size {
+        let size = self.outer_size(Size::default());
+        Size::new(size.width.min(max_width), size.height)
+    }
 }
 
 impl Widget for Block {
     fn render(self, area: Rect, frame: &mut Frame) {
+        self.render_ref(area, frame);
+    }
+}
+
+impl WidgetRef for Block {
+    fn render_ref(&self, area: Rect, frame: &mut Frame) {
         let (h, v, tl, tr, bl, br) = self.border_type.get_chars();
-        frame.with_style(self.style, |f| {
+        let border_style = frame.role_style(Role::Border).patch(self.style);
+        let title_style = frame.role_style(Role::Title).patch(self.title_style);
+        frame.with_style(border_style, |f| {
             f.render_area(area, |f| {
                 let width = f.width();
                 let height = f.height();
+                if width == 0 || height == 0 {
+                    return;
+                }
                 let mut buf = [0u8; 4];
 
                 // 1. Draw Sides (Edge-to-Edge)
@@ -251,15 +310,17 @@ impl Widget for Block {
                 }
 
                 // 3. Draw Title
-                if let Some(t) = self.title {
-                    let style = if self.title_style == Style::default() {
-                        self.style
-                    } else {
-                        self.title_style
-                    };
-
-                    f.with_style(style, |f| {
-                        f.write_str(2, 0, &format!(" {} ", t));
+                if let Some(t) = &self.title {
+                    let mut padded = Line::new(Vec::with_capacity(t.spans.len() + 2));
+                    padded.alignment = t.alignment;
+                    padded.push_span(" ");
+                    padded.spans.extend(t.spans.iter().cloned());
+                    padded.push_span(" ");
+
+                    let title_area =
+                        Rect::new(area.x.saturating_add(2), area.y, width.saturating_sub(4), 1);
+                    f.with_style(title_style, |f| {
+                        f.render_line(&padded, title_area);
                     });
                 }
             });
@@ -337,6 +398,63 @@ mod tests {
         assert_eq!(buffer.get(3, 0).style.foreground, Some(Color::Red));
     }
 
+    #[test]
+    fn test_block_uses_theme_roles() {
+        use crate::{Color, Modifier, Theme};
+
+        let theme = Theme::new()
+            .with(Role::Border, Style::new().fg(Color::Blue).bg(Color::Black))
+            .with(Role::Title, Style::new().modifier(Modifier::BOLD));
+
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        frame.set_theme(theme);
+        Block::new()
+            .borders(Borders::ALL)
+            .style(Style::new().fg(Color::Red))
+            .title("T")
+            .render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        // The block's own style wins, unset fields come from the theme.
+        let corner = buffer.get(0, 0).style;
+        assert_eq!(corner.foreground, Some(Color::Red));
+        assert_eq!(corner.background, Some(Color::Black));
+
+        let title = buffer.get(3, 0);
+        assert_eq!(title.symbol, 'T');
+        assert_eq!(title.style.foreground, Some(Color::Red));
+        assert!(title.style.modifiers.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_block_render_rich_title() {
+        use crate::{Alignment, Color, Modifier, Span};
+
+        let mut buffer = Buffer::new(12, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 12, 3));
+        let bold = Style::new().modifier(Modifier::BOLD);
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(Style::new().fg(Color::Blue))
+            .title(
+                Line::new(vec![Span::styled("A", bold), Span::raw("b")])
+                    .alignment(Alignment::Right),
+            );
+
+        block.render(Rect::new(0, 0, 12, 3), &mut frame);
+
+        // " Ab " ends two cells before the right edge.
+        assert_eq!(buffer.get(7, 0).symbol, 'A');
+        // The bold span keeps the block's color.
+        assert_eq!(
+            buffer.get(7, 0).style,
+            Style::new().fg(Color::Blue).patch(bold)
+        );
+        assert_eq!(buffer.get(8, 0).symbol, 'b');
+        assert_eq!(buffer.get(8, 0).style.foreground, Some(Color::Blue));
+        assert_eq!(buffer.get(10, 0).symbol, R_BORDER_H);
+    }
+
     #[test]
     fn test_block_inner_area() {
         let block = Block::new().borders(Borders::ALL).padding(0);
@@ -349,6 +467,18 @@ mod tests {
         assert_eq!(inner.height, 8);
     }
 
+    #[test]
+    fn test_block_outer_size() {
+        let block = Block::new().borders(Borders::ALL).padding_x(1);
+        let outer = block.outer_size(Size::new(5, 2));
+        assert_eq!(outer, Size::new(9, 4));
+        assert_eq!(block.inner(Rect::new(0, 0, 9, 4)).width, 5);
+
+        let titled = Block::new().borders(Borders::LEFT).title("Title");
+        assert_eq!(titled.outer_size(Size::new(2, 0)), Size::new(11, 1));
+        assert_eq!(titled.preferred_size(8), Size::new(8, 1));
+    }
+
     #[test]
     fn test_block_padding() {
         let block = Block::new().borders(Borders::NONE).padding(2);
@@ -375,4 +505,19 @@ mod tests {
         assert_eq!(inner.width, 6); // 10 - (2 * 2)
         assert_eq!(inner.height, 8); // 10 - (1 * 2)
     }
+
+    #[test]
+    fn test_block_extreme_sizes() {
+        let block = Block::new().borders(Borders::ALL).padding(u16::MAX);
+        let inner = block.inner(Rect::new(u16::MAX, u16::MAX, 10, 10));
+        assert_eq!(inner, Rect::new(u16::MAX, u16::MAX, 0, 0));
+
+        // Rendering into an empty area must not underflow.
+        let mut buffer = Buffer::new(1, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 1, 1));
+        Block::new()
+            .borders(Borders::ALL)
+            .render(Rect::new(0, 0, 0, 0), &mut frame);
+        assert_eq!(buffer.get(0, 0).symbol, ' ');
+    }
 }
@@ -0,0 +1,345 @@
+//! Widgets that display progress as a filled bar.
+
+use std::cmp::Ordering;
+
+use crate::{
+    Frame, Line, Modifier, Rect, Role, Style,
+    widgets::{Block, Widget, WidgetRef},
+};
+
+/// The blocks filling one to seven eighths of a cell, from the left.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+/// A completely filled cell.
+const FULL_BLOCK: char = '█';
+/// The filled part of a [`LineGauge`].
+const LINE_FILLED: char = '━';
+/// The unfilled part of a [`LineGauge`].
+const LINE_UNFILLED: char = '─';
+
+/// Helper to turn `value` out of `max` into a ratio between 0 and 1.
+fn ratio_of(value: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Helper to build the default label, the ratio as a percentage.
+fn percent_label(ratio: f64) -> Line {
+    Line::raw(format!("{}%", (ratio * 100.0).round()))
+}
+
+/// A bar that fills an area in proportion to a ratio, with a centered label.
+///
+/// The bar has a precision of an eighth of a cell. Filled cells are drawn
+/// with the theme's [`Role::Highlight`] style, and the part of the label
+/// over them is reversed so it stays readable.
+///
+/// # Example
+/// ```
+/// use phosphor::{Buffer, Frame, Rect, widgets::Gauge};
+///
+/// let mut buffer = Buffer::new(10, 1);
+/// let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+/// frame.render_widget(Gauge::new().value(3.0, 4.0).label(""), Rect::new(0, 0, 10, 1));
+///
+/// let bar: String = (0..10).map(|x| buffer.get(x, 0).symbol).collect();
+/// assert_eq!(bar, "███████▌  ");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Gauge {
+    ratio: f64,
+    label: Option<Line>,
+    block: Option<Block>,
+    filled_style: Style,
+    unfilled_style: Style,
+}
+
+impl Gauge {
+    /// Creates an empty gauge.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the filled ratio, clamped between 0 and 1.
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the filled ratio to `value` out of `max`.
+    ///
+    /// A `max` of zero or less gives an empty gauge.
+    pub fn value(self, value: f64, max: f64) -> Self {
+        self.ratio(ratio_of(value, max))
+    }
+
+    /// Sets the label. Defaults to the ratio as a percentage, e.g. `42%`.
+    pub fn label<L: Into<Line>>(mut self, label: L) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Wraps the gauge in a block.
+    pub fn block(mut self, block: Block) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the style of the filled part, patched over the theme's
+    /// [`Role::Highlight`] style.
+    pub fn filled_style(mut self, style: Style) -> Self {
+        self.filled_style = style;
+        self
+    }
+
+    /// Sets the style of the unfilled part, patched over the theme's
+    /// [`Role::Text`] style.
+    pub fn unfilled_style(mut self, style: Style) -> Self {
+        self.unfilled_style = style;
+        self
+    }
+}
+
+impl Widget for Gauge {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        self.render_ref(area, frame);
+    }
+}
+
+impl WidgetRef for Gauge {
+    fn render_ref(&self, area: Rect, frame: &mut Frame) {
+        let area = match &self.block {
+            Some(block) => {
+                block.render_ref(area, frame);
+                block.inner(area)
+            }
+            None => area,
+        };
+        if area.is_empty() {
+            return;
+        }
+
+        let filled = frame.role_style(Role::Highlight).patch(self.filled_style);
+        let unfilled = frame.role_style(Role::Text).patch(self.unfilled_style);
+
+        // 1. The bar, in eighths of a cell.
+        let eighths = (self.ratio * area.width as f64 * 8.0).round() as u32;
+        let full = (eighths / 8) as u16;
+        let partial = PARTIAL_BLOCKS[(eighths % 8) as usize];
+        let mut buf = [0u8; 4];
+        frame.render_area(area, |f| {
+            for y in 0..area.height {
+                for x in 0..area.width {
+                    let (symbol, style) = match x.cmp(&full) {
+                        Ordering::Less => (FULL_BLOCK, filled),
+                        Ordering::Equal if partial != ' ' => (partial, filled),
+                        _ => (' ', unfilled),
+                    };
+                    f.write_str_with_style(x, y, symbol.encode_utf8(&mut buf), style);
+                }
+            }
+        });
+
+        // 2. The label, centered, reversed where it covers filled cells.
+        let mut label = self
+            .label
+            .clone()
+            .unwrap_or_else(|| percent_label(self.ratio));
+        label.alignment = None;
+        let width = (label.width().min(area.width as usize)) as u16;
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + area.height / 2;
+        let label_area = Rect::new(x, y, width, 1);
+        let covered = full.saturating_sub(x - area.x).min(width);
+
+        frame.with_style(unfilled, |f| f.render_line(&label, label_area));
+        let on_filled = filled.modifier(Modifier::REVERSED);
+        frame.with_style(on_filled, |f| {
+            f.render_line(&label, Rect::new(x, y, covered, 1));
+        });
+    }
+}
+
+/// A progress bar drawn as a line after its label, on a single row.
+///
+/// The filled part of the line uses the theme's [`Role::Highlight`] style
+/// and the rest [`Role::Muted`].
+///
+/// # Example
+/// ```
+/// use phosphor::{Buffer, Frame, Rect, widgets::LineGauge};
+///
+/// let mut buffer = Buffer::new(12, 1);
+/// let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 12, 1));
+/// frame.render_widget(LineGauge::new().ratio(0.5), Rect::new(0, 0, 12, 1));
+///
+/// let line: String = (0..12).map(|x| buffer.get(x, 0).symbol).collect();
+/// assert_eq!(line, "50% ━━━━────");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LineGauge {
+    ratio: f64,
+    label: Option<Line>,
+    block: Option<Block>,
+    filled_style: Style,
+    unfilled_style: Style,
+}
+
+impl LineGauge {
+    /// Creates an empty line gauge.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the filled ratio, clamped between 0 and 1.
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the filled ratio to `value` out of `max`.
+    ///
+    /// A `max` of zero or less gives an empty gauge.
+    pub fn value(self, value: f64, max: f64) -> Self {
+        self.ratio(ratio_of(value, max))
+    }
+
+    /// Sets the label. Defaults to the ratio as a percentage, e.g. `42%`.
+    ///
+    /// An empty label leaves the whole row to the line.
+    pub fn label<L: Into<Line>>(mut self, label: L) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Wraps the gauge in a block.
+    pub fn block(mut self, block: Block) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the style of the filled part, patched over the theme's
+    /// [`Role::Highlight`] style.
+    pub fn filled_style(mut self, style: Style) -> Self {
+        self.filled_style = style;
+        self
+    }
+
+    /// Sets the style of the unfilled part, patched over the theme's
+    /// [`Role::Muted`] style.
+    pub fn unfilled_style(mut self, style: Style) -> Self {
+        self.unfilled_style = style;
+        self
+    }
+}
+
+impl Widget for LineGauge {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        self.render_ref(area, frame);
+    }
+}
+
+impl WidgetRef for LineGauge {
+    fn render_ref(&self, area: Rect, frame: &mut Frame) {
+        let area = match &self.block {
+            Some(block) => {
+                block.render_ref(area, frame);
+                block.inner(area)
+            }
+            None => area,
+        };
+        if area.is_empty() {
+            return;
+        }
+
+        let label = self
+            .label
+            .clone()
+            .unwrap_or_else(|| percent_label(self.ratio));
+        let mut row = Rect::new(area.x, area.y, area.width, 1);
+        if !label.is_empty() {
+            let label_width = (label.width() + 1).min(area.width as usize) as u16;
+            let label_area = row.take_left(label_width);
+            let text = frame.role_style(Role::Text);
+            frame.with_style(text, |f| f.render_line(&label, label_area));
+        }
+
+        let filled = frame.role_style(Role::Highlight).patch(self.filled_style);
+        let unfilled = frame.role_style(Role::Muted).patch(self.unfilled_style);
+        let full = (self.ratio * row.width as f64).round() as u16;
+        let mut buf = [0u8; 4];
+        frame.render_area(row, |f| {
+            for x in 0..row.width {
+                let (symbol, style) = if x < full {
+                    (LINE_FILLED, filled)
+                } else {
+                    (LINE_UNFILLED, unfilled)
+                };
+                f.write_str_with_style(x, 0, symbol.encode_utf8(&mut buf), style);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color, Theme, widgets::Borders};
+
+    #[test]
+    fn test_gauge_sub_cell_fill_and_label() {
+        let mut buffer = Buffer::new(10, 3);
+        let area = Rect::new(0, 0, 10, 3);
+        let mut frame = Frame::new(&mut buffer, area);
+        frame.set_theme(Theme::new().with(Role::Highlight, Style::new().fg(Color::Green)));
+
+        frame.render_widget(Gauge::new().ratio(0.45), area);
+
+        assert_eq!(buffer.row_symbols(0), "████▌     ");
+        assert_eq!(buffer.row_symbols(1), "███45%    ");
+        let on_filled = buffer.get(3, 1).style;
+        assert_eq!(on_filled.foreground, Some(Color::Green));
+        assert!(on_filled.modifiers.contains(Modifier::REVERSED));
+        assert!(
+            !buffer
+                .get(4, 1)
+                .style
+                .modifiers
+                .contains(Modifier::REVERSED)
+        );
+        assert_eq!(buffer.get(4, 1).style.foreground, None);
+    }
+
+    #[test]
+    fn test_gauge_value_and_block() {
+        let mut buffer = Buffer::new(6, 3);
+        let area = Rect::new(0, 0, 6, 3);
+        let mut frame = Frame::new(&mut buffer, area);
+        let gauge = Gauge::new()
+            .value(5.0, 4.0)
+            .label("ok")
+            .block(Block::new().borders(Borders::ALL));
+
+        frame.render_widget(gauge, area);
+
+        assert_eq!(buffer.row_symbols(1), "│█ok█│");
+        assert_eq!(Gauge::new().value(1.0, 0.0).ratio, 0.0);
+    }
+
+    #[test]
+    fn test_line_gauge() {
+        let mut buffer = Buffer::new(8, 2);
+        let area = Rect::new(0, 0, 8, 2);
+        let mut frame = Frame::new(&mut buffer, area);
+        frame.set_theme(Theme::new().with(Role::Muted, Style::new().fg(Color::BrightBlack)));
+
+        frame.render_widget(LineGauge::new().value(1.0, 4.0).label("cpu"), area);
+        frame.render_widget(LineGauge::new().ratio(1.0).label(""), Rect::new(0, 1, 8, 1));
+
+        assert_eq!(buffer.row_symbols(0), "cpu ━───");
+        assert_eq!(buffer.row_symbols(1), "━━━━━━━━");
+        assert_eq!(buffer.get(5, 0).style.foreground, Some(Color::BrightBlack));
+    }
+}
@@ -0,0 +1,420 @@
+//! A widget that displays a scrollable list of items with a selection.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    Frame, KeyCode, KeyEvent, Line, Rect, Role, Size, Style,
+    widgets::{Measure, StatefulWidget, Widget, selection::Selection},
+};
+
+/// The order in which a [`List`] lays out its items.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ListDirection {
+    /// The first item is at the top.
+    #[default]
+    TopToBottom,
+    /// The first item is at the bottom, as in a chat log.
+    BottomToTop,
+}
+
+/// The selection and scroll offset of a [`List`], kept between frames.
+///
+/// The navigation helpers don't know how many items there are; rendering
+/// clamps the selection to the items and scrolls so it stays visible.
+///
+/// Marks are never clamped: a mark past the end of the list is kept (and
+/// returned by [`marked`](Self::marked)) until the app removes it, for
+/// example with [`clear_marked`](Self::clear_marked) when the items change.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListState {
+    selection: Selection,
+    marked: BTreeSet<usize>,
+}
+
+impl ListState {
+    /// Creates a state with nothing selected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the selected item.
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.select(selected);
+        self
+    }
+
+    /// Sets the index of the first visible item.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.selection.offset = offset;
+        self
+    }
+
+    /// Returns the index of the selected item, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.selection.selected
+    }
+
+    /// Returns the index of the first visible item.
+    pub fn offset(&self) -> usize {
+        self.selection.offset
+    }
+
+    /// Selects an item, or clears the selection and scrolls to the top.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selection.select(index);
+    }
+
+    /// Selects the next item, or the first one if nothing is selected.
+    pub fn select_next(&mut self) {
+        self.selection.select_next();
+    }
+
+    /// Selects the previous item, or the last one if nothing is selected.
+    pub fn select_previous(&mut self) {
+        self.selection.select_previous();
+    }
+
+    /// Selects the first item.
+    pub fn select_first(&mut self) {
+        self.selection.select_first();
+    }
+
+    /// Selects the last item.
+    pub fn select_last(&mut self) {
+        self.selection.select_last();
+    }
+
+    /// Moves the selection down by one page of visible items.
+    pub fn page_down(&mut self) {
+        self.selection.page_down();
+    }
+
+    /// Moves the selection up by one page of visible items.
+    pub fn page_up(&mut self) {
+        self.selection.page_up();
+    }
+
+    /// Returns `true` if the item at `index` is marked.
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+
+    /// Marks or unmarks the item at `index`.
+    pub fn set_marked(&mut self, index: usize, marked: bool) {
+        if marked {
+            self.marked.insert(index);
+        } else {
+            self.marked.remove(&index);
+        }
+    }
+
+    /// Marks the selected item, or unmarks it if it is already marked.
+    pub fn toggle_marked(&mut self) {
+        if let Some(index) = self.selected() {
+            let marked = self.is_marked(index);
+            self.set_marked(index, !marked);
+        }
+    }
+
+    /// Returns the indices of the marked items, in increasing order.
+    pub fn marked(&self) -> impl Iterator<Item = usize> + '_ {
+        self.marked.iter().copied()
+    }
+
+    /// Unmarks every item.
+    pub fn clear_marked(&mut self) {
+        self.marked.clear();
+    }
+
+    /// Updates the state for a key press. Returns `true` if the key was used.
+    ///
+    /// * `Up`/`k` and `Down`/`j` select the previous and next item.
+    /// * `PageUp` and `PageDown` move by a page.
+    /// * `Home`/`g` and `End`/`G` select the first and last item.
+    /// * `Space` toggles the mark on the selected item.
+    ///
+    /// Keys follow the item order, so in a [`ListDirection::BottomToTop`]
+    /// list, `Down` moves the selection up the screen.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.code == KeyCode::Char(' ') {
+            self.toggle_marked();
+            return true;
+        }
+        self.selection.handle_key(key)
+    }
+
+    /// Helper to clamp the selection to `len` items shown in `height` rows,
+    /// scrolling so that it is visible. Marks are left alone.
+    fn clamp(&mut self, len: usize, height: usize) {
+        self.selection.clamp(len, height, |_| 1);
+    }
+}
+
+/// A widget that displays a list of items, one per row.
+///
+/// The selected item of the [`ListState`] is drawn with the theme's
+/// [`Role::Selection`] style and marked items with [`Role::Highlight`].
+///
+/// # Example
+/// ```
+/// use phosphor::{Buffer, Frame, Rect, widgets::{List, ListState}};
+///
+/// let mut buffer = Buffer::new(10, 2);
+/// let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+/// let mut state = ListState::new().with_selected(Some(2));
+///
+/// let list = List::new(["one", "two", "three"]).highlight_symbol("> ");
+/// frame.render_stateful_widget(list, Rect::new(0, 0, 10, 2), &mut state);
+///
+/// assert_eq!(state.offset(), 1);
+/// assert_eq!(buffer.get(0, 1).symbol, '>');
+/// ```
+pub struct List {
+    items: Vec<Line>,
+    style: Style,
+    highlight_style: Style,
+    marked_style: Style,
+    highlight_symbol: String,
+    direction: ListDirection,
+}
+
+impl List {
+    /// Creates a list from anything convertible to [`Line`]s.
+    pub fn new<I, T>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Line>,
+    {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            style: Style::default(),
+            highlight_style: Style::default(),
+            marked_style: Style::default(),
+            highlight_symbol: String::new(),
+            direction: ListDirection::default(),
+        }
+    }
+
+    /// Sets the base style of the items, patched over the theme's
+    /// [`Role::Text`] style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the selected item, patched over the theme's
+    /// [`Role::Selection`] style.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Sets the style of marked items, patched over the theme's
+    /// [`Role::Highlight`] style.
+    pub fn marked_style(mut self, style: Style) -> Self {
+        self.marked_style = style;
+        self
+    }
+
+    /// Sets the symbol drawn before the selected item.
+    ///
+    /// Other items are indented by the same width so they stay aligned.
+    pub fn highlight_symbol(mut self, symbol: &str) -> Self {
+        self.highlight_symbol = symbol.to_string();
+        self
+    }
+
+    /// Sets the order in which items are laid out.
+    pub fn direction(mut self, direction: ListDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Helper to get the width of the highlight symbol column.
+    fn symbol_width(&self) -> u16 {
+        Line::raw(self.highlight_symbol.as_str())
+            .width()
+            .min(u16::MAX as usize) as u16
+    }
+}
+
+impl StatefulWidget for List {
+    type State = ListState;
+
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut ListState) {
+        let height = area.height as usize;
+        state.clamp(self.items.len(), height);
+        if area.is_empty() {
+            return;
+        }
+
+        let base = frame.role_style(Role::Text).patch(self.style);
+        let selected_style = frame
+            .role_style(Role::Selection)
+            .patch(self.highlight_style);
+        let marked_style = frame.role_style(Role::Highlight).patch(self.marked_style);
+        let symbol_width = self.symbol_width().min(area.width);
+        let blank = Line::raw(" ".repeat(area.width as usize));
+        let symbol = Line::raw(self.highlight_symbol.as_str());
+
+        let visible = self
+            .items
+            .iter()
+            .enumerate()
+            .skip(state.offset())
+            .take(height);
+        for (row, (index, item)) in visible.enumerate() {
+            let y = match self.direction {
+                ListDirection::TopToBottom => row,
+                ListDirection::BottomToTop => height - 1 - row,
+            };
+            let row_area = Rect::new(area.x, area.y + y as u16, area.width, 1);
+            let is_selected = state.selected() == Some(index);
+
+            let mut style = base;
+            if state.is_marked(index) {
+                style = style.patch(marked_style);
+            }
+            if is_selected {
+                style = style.patch(selected_style);
+            }
+
+            let (symbol_area, content) = row_area.split_at_x(symbol_width);
+            frame.with_style(style, |f| {
+                // Fill the row so the background covers the whole width.
+                if style != base {
+                    f.render_line(&blank, row_area);
+                }
+                if is_selected {
+                    f.render_line(&symbol, symbol_area);
+                }
+                f.render_line(item, content);
+            });
+        }
+    }
+}
+
+impl Widget for List {
+    /// Draws the list without a selection, from the first item.
+    fn render(self, area: Rect, frame: &mut Frame) {
+        frame.render_stateful_widget(self, area, &mut ListState::default());
+    }
+}
+
+impl Measure for List {
+    /// Returns the width of the widest item plus the highlight symbol, and
+    /// one row per item.
+    fn preferred_size(&self, max_width: u16) -> Size {
+        let width = self.items.iter().map(Line::width).max().unwrap_or(0);
+        let width = (self.symbol_width() as usize).saturating_add(width);
+        Size::new(
+            width.min(max_width as usize) as u16,
+            self.items.len().min(u16::MAX as usize) as u16,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color, Theme};
+
+    #[test]
+    fn test_list_render_with_selection() {
+        let mut buffer = Buffer::new(8, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 8, 3));
+        frame.set_theme(Theme::new().with(Role::Selection, Style::new().bg(Color::Blue)));
+        let mut state = ListState::new().with_selected(Some(1));
+
+        let list = List::new(["a", "b", "c"]).highlight_symbol("> ");
+        frame.render_stateful_widget(list, Rect::new(0, 0, 8, 3), &mut state);
+
+        assert_eq!(buffer.row_symbols(0), "  a     ");
+        assert_eq!(buffer.row_symbols(1), "> b     ");
+        assert_eq!(buffer.get(7, 1).style.background, Some(Color::Blue));
+        assert_eq!(buffer.get(7, 0).style.background, None);
+    }
+
+    #[test]
+    fn test_list_scrolls_to_selection() {
+        let mut buffer = Buffer::new(3, 2);
+        let area = Rect::new(0, 0, 3, 2);
+        let items = ["0", "1", "2", "3", "4"];
+        let mut state = ListState::new();
+        let mut render = |list: List, state: &mut ListState| {
+            Frame::new(&mut buffer, area).render_stateful_widget(list, area, state);
+        };
+
+        state.select_last();
+        render(List::new(items), &mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(4), 3));
+
+        state.page_up();
+        render(List::new(items), &mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(2), 2));
+
+        state.select(Some(0));
+        render(List::new(items), &mut state);
+        assert_eq!(state.offset(), 0);
+
+        // Fewer items than before clamp the selection.
+        state.select(Some(4));
+        render(List::new(["x"]), &mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(0), 0));
+        render(List::new([""; 0]), &mut state);
+        assert_eq!(state.selected(), None);
+        assert_eq!(buffer.row_symbols(0), "x  ");
+    }
+
+    #[test]
+    fn test_list_bottom_to_top() {
+        let mut buffer = Buffer::new(2, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 2, 3));
+        let mut state = ListState::new().with_selected(Some(3));
+
+        let list = List::new(["a", "b", "c", "d"]).direction(ListDirection::BottomToTop);
+        frame.render_stateful_widget(list, Rect::new(0, 0, 2, 3), &mut state);
+
+        assert_eq!(state.offset(), 1);
+        assert_eq!(buffer.row_symbols(0), "d ");
+        assert_eq!(buffer.row_symbols(2), "b ");
+    }
+
+    #[test]
+    fn test_list_keys_and_marks() {
+        let mut state = ListState::new();
+        let key = |code| KeyEvent::new(code);
+
+        assert!(state.handle_key(&key(KeyCode::Down)));
+        assert!(state.handle_key(&key(KeyCode::Char(' '))));
+        assert!(state.handle_key(&key(KeyCode::Char('j'))));
+        assert!(state.handle_key(&key(KeyCode::Char(' '))));
+        assert!(state.handle_key(&key(KeyCode::Up)));
+        assert!(state.handle_key(&key(KeyCode::Char(' '))));
+        assert!(!state.handle_key(&key(KeyCode::Char('x'))));
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(state.marked().collect::<Vec<_>>(), vec![1]);
+
+        let mut buffer = Buffer::new(2, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 2, 3));
+        frame.set_theme(Theme::new().with(Role::Highlight, Style::new().fg(Color::Green)));
+        state.set_marked(5, true);
+        frame.render_stateful_widget(List::new(["a", "b"]), Rect::new(0, 0, 2, 3), &mut state);
+
+        assert_eq!(buffer.get(0, 1).style.foreground, Some(Color::Green));
+        assert_eq!(buffer.get(0, 0).style.foreground, None);
+        // Marks past the end survive a render, in case the list grows back.
+        assert!(state.is_marked(5));
+        assert_eq!(state.marked().collect::<Vec<_>>(), vec![1, 5]);
+    }
+}
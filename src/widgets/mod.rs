@@ -1,17 +1,73 @@
 //! The `widgets` module provides reusable UI components.
 
-use crate::{Frame, Rect};
+use crate::{Frame, Rect, Size};
 
 pub mod block;
+pub mod gauge;
+pub mod list;
 pub mod scrollable;
+mod selection;
+pub mod table;
 pub mod text;
+pub mod text_input;
 
 pub use block::{Block, BorderType, Borders};
-pub use scrollable::Scrollable;
+pub use gauge::{Gauge, LineGauge};
+pub use list::{List, ListDirection, ListState};
+pub use scrollable::{ScrollState, Scrollable};
+pub use table::{Row, Table, TableCell, TableState};
 pub use text::Text;
+pub use text_input::{InputState, TextInput};
 
 /// The core trait for all UI components.
 pub trait Widget {
     /// Draws the widget into the given area of the frame.
     fn render(self, area: Rect, frame: &mut Frame);
 }
+
+/// A widget that keeps state between frames, such as a scroll offset or a
+/// selection.
+///
+/// The state lives in the application and is passed to every render, which
+/// may update it, e.g. to clamp an offset to the size of the content.
+pub trait StatefulWidget {
+    /// The state kept between frames.
+    type State;
+
+    /// Draws the widget into the given area of the frame, updating its state.
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut Self::State);
+}
+
+/// A widget that can be drawn by reference, so it can be kept and drawn on
+/// every frame without being rebuilt.
+///
+/// Every `WidgetRef` is also a [`Widget`] by reference, so `&widget` can be
+/// passed to [`Frame::render_widget`].
+pub trait WidgetRef {
+    /// Draws the widget into the given area of the frame.
+    fn render_ref(&self, area: Rect, frame: &mut Frame);
+}
+
+impl<W: WidgetRef + ?Sized> Widget for &W {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        self.render_ref(area, frame);
+    }
+}
+
+/// A widget that can report how much space its content needs.
+///
+/// Used by [`Layout::split_measured`](crate::Layout::split_measured) to
+/// resolve [`Constraint::Fit`](crate::Constraint::Fit) segments.
+pub trait Measure {
+    /// Returns the size the widget would like, given at most `max_width`
+    /// columns. The width never exceeds `max_width`.
+    fn preferred_size(&self, max_width: u16) -> Size;
+
+    /// Returns the smallest size the widget can be drawn at without losing
+    /// content, given at most `max_width` columns.
+    ///
+    /// Defaults to the preferred size.
+    fn min_size(&self, max_width: u16) -> Size {
+        self.preferred_size(max_width)
+    }
+}
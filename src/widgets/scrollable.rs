@@ -1,6 +1,32 @@
 //! A widget that allows scrolling its content.
 
-use crate::{Buffer, Frame, Rect, Widget};
+use crate::{Buffer, Frame, Measure, Rect, Size, Widget};
+
+/// The scroll offset of a [`Scrollable`], kept between frames.
+///
+/// Rendering clamps the offset so the viewport never scrolls past the
+/// content, so it can be moved freely in response to events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollState {
+    /// The horizontal offset, in columns.
+    pub x: u16,
+    /// The vertical offset, in rows.
+    pub y: u16,
+}
+
+impl ScrollState {
+    /// Creates a scroll state at the given offset.
+    pub fn new(x: u16, y: u16) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the offset by `dx` columns and `dy` rows, stopping at zero.
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let clamp = |v: u16, d: i32| (v as i32 + d).clamp(0, u16::MAX as i32) as u16;
+        self.x = clamp(self.x, dx);
+        self.y = clamp(self.y, dy);
+    }
+}
 
 /// A wrapper widget that renders its child into a virtual buffer and displays a viewport.
 pub struct Scrollable<W> {
@@ -46,6 +72,7 @@ impl<W: Widget> Widget for Scrollable<W> {
         let mut buffer = Buffer::new(self.width, self.height);
         let tmp_area = Rect::new(0, 0, self.width, self.height);
         let mut tmp_frame = Frame::new(&mut buffer, tmp_area);
+        tmp_frame.set_theme(*frame.theme());
 
         // 2. Render content into temp buffer
         self.content.render(tmp_area, &mut tmp_frame);
@@ -63,6 +90,26 @@ impl<W: Widget> Widget for Scrollable<W> {
     }
 }
 
+impl<W: Widget> crate::StatefulWidget for Scrollable<W> {
+    type State = ScrollState;
+
+    /// Draws the content scrolled to the offset in `state`, after clamping
+    /// it to the virtual size. The offset set with [`Scrollable::scroll`] is
+    /// ignored.
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut ScrollState) {
+        state.x = state.x.min(self.width.saturating_sub(area.width));
+        state.y = state.y.min(self.height.saturating_sub(area.height));
+        Widget::render(self.scroll(state.x, state.y), area, frame);
+    }
+}
+
+impl<W> Measure for Scrollable<W> {
+    /// Returns the virtual size, clipped to `max_width`.
+    fn preferred_size(&self, max_width: u16) -> Size {
+        Size::new(self.width.min(max_width), self.height)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,4 +132,39 @@ mod tests {
         assert_eq!(buffer.get(1, 0).symbol, 'o');
         assert_eq!(buffer.get(4, 0).symbol, 'd');
     }
+
+    #[test]
+    fn test_scrollable_large_virtual_size() {
+        let mut buffer = Buffer::new(5, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 2));
+
+        // 1000 * 1000 cells would overflow the old u16 buffer math.
+        let text = Text::new("Far");
+        let scrollable = Scrollable::new(text).virtual_size(1000, 1000).scroll(0, 0);
+
+        scrollable.render(Rect::new(0, 0, 5, 2), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, 'F');
+        assert_eq!(buffer.get(2, 0).symbol, 'r');
+    }
+
+    #[test]
+    fn test_scrollable_state_is_clamped() {
+        let mut buffer = Buffer::new(5, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 2));
+        let mut state = ScrollState::new(0, 0);
+        state.scroll_by(-3, 10);
+        assert_eq!(state, ScrollState::new(0, 10));
+
+        let text = Text::new("a\nb\nc\nd");
+        frame.render_stateful_widget(
+            Scrollable::new(text).virtual_size(5, 4),
+            Rect::new(0, 0, 5, 2),
+            &mut state,
+        );
+
+        assert_eq!(state, ScrollState::new(0, 2));
+        assert_eq!(buffer.get(0, 0).symbol, 'c');
+        assert_eq!(buffer.get(0, 1).symbol, 'd');
+    }
 }
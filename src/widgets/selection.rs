@@ -0,0 +1,107 @@
+//! The selection and scrolling shared by [`ListState`](super::ListState)
+//! and [`TableState`](super::TableState).
+
+use crate::{KeyCode, KeyEvent};
+
+/// A selected row and the first visible row of a scrollable widget.
+///
+/// The navigation helpers don't know how many rows there are; [`clamp`]
+/// fixes the selection up when the widget is rendered.
+///
+/// [`clamp`]: Selection::clamp
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Selection {
+    pub(crate) selected: Option<usize>,
+    pub(crate) offset: usize,
+    /// The number of rows shown by the last render, used to move by pages.
+    pub(crate) viewport: usize,
+}
+
+impl Selection {
+    /// Selects a row, or clears the selection and scrolls to the top.
+    pub(crate) fn select(&mut self, index: Option<usize>) {
+        self.selected = index;
+        if index.is_none() {
+            self.offset = 0;
+        }
+    }
+
+    /// Selects the next row, or the first one if nothing is selected.
+    pub(crate) fn select_next(&mut self) {
+        self.selected = Some(self.selected.map_or(0, |i| i.saturating_add(1)));
+    }
+
+    /// Selects the previous row, or the last one if nothing is selected.
+    pub(crate) fn select_previous(&mut self) {
+        self.selected = Some(self.selected.map_or(usize::MAX, |i| i.saturating_sub(1)));
+    }
+
+    /// Selects the first row.
+    pub(crate) fn select_first(&mut self) {
+        self.selected = Some(0);
+    }
+
+    /// Selects the last row.
+    pub(crate) fn select_last(&mut self) {
+        self.selected = Some(usize::MAX);
+    }
+
+    /// Moves the selection down by the number of rows last shown.
+    pub(crate) fn page_down(&mut self) {
+        let page = self.viewport.max(1);
+        self.selected = Some(self.selected.map_or(0, |i| i.saturating_add(page)));
+    }
+
+    /// Moves the selection up by the number of rows last shown.
+    pub(crate) fn page_up(&mut self) {
+        let page = self.viewport.max(1);
+        self.selected = Some(self.selected.map_or(0, |i| i.saturating_sub(page)));
+    }
+
+    /// Updates the selection for a navigation key. Returns `true` if the key
+    /// was used.
+    pub(crate) fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.select_next(),
+            KeyCode::PageUp => self.page_up(),
+            KeyCode::PageDown => self.page_down(),
+            KeyCode::Home | KeyCode::Char('g') => self.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.select_last(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Clamps the selection to `len` rows shown in `height` lines, where row
+    /// `i` takes `line_count(i)` lines, and scrolls so that the selected row
+    /// is fully visible when it fits.
+    pub(crate) fn clamp(&mut self, len: usize, height: usize, line_count: impl Fn(usize) -> usize) {
+        self.selected = self.selected.filter(|_| len > 0).map(|i| i.min(len - 1));
+        self.offset = self.offset.min(len.saturating_sub(1));
+
+        if let Some(selected) = self.selected {
+            self.offset = self.offset.min(selected);
+            let mut used: usize = (self.offset..=selected).map(&line_count).sum();
+            while self.offset < selected && used > height {
+                used -= line_count(self.offset);
+                self.offset += 1;
+            }
+        }
+
+        // Fill the viewport rather than leaving space below the last row.
+        let mut below: usize = (self.offset..len).map(&line_count).sum();
+        while self.offset > 0 && below + line_count(self.offset - 1) <= height {
+            self.offset -= 1;
+            below += line_count(self.offset);
+        }
+
+        let mut lines = 0;
+        self.viewport = (self.offset..len)
+            .take_while(|&i| {
+                lines += line_count(i);
+                lines <= height
+            })
+            .count();
+    }
+}
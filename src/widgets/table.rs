@@ -0,0 +1,510 @@
+//! A widget that displays rows of cells aligned in columns.
+
+use crate::{
+    Constraint, Direction, Frame, KeyEvent, Layout, Line, Rect, Role, Span, Style, StyledText,
+    text::char_width,
+    widgets::{StatefulWidget, Widget, selection::Selection},
+};
+
+/// The character drawn at the end of truncated cell content.
+const ELLIPSIS: &str = "…";
+
+/// A cell of a [`Table`], holding one or more lines of styled content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableCell {
+    content: StyledText,
+    style: Style,
+}
+
+impl TableCell {
+    /// Creates a cell. Strings are split on newlines.
+    pub fn new<T: Into<StyledText>>(content: T) -> Self {
+        Self {
+            content: content.into(),
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the style of the cell, patched over the row's style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl<T: Into<StyledText>> From<T> for TableCell {
+    fn from(content: T) -> Self {
+        Self::new(content)
+    }
+}
+
+/// A row of a [`Table`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Row {
+    cells: Vec<TableCell>,
+    height: Option<u16>,
+    style: Style,
+}
+
+impl Row {
+    /// Creates a row from anything convertible to [`TableCell`]s.
+    pub fn new<I, T>(cells: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<TableCell>,
+    {
+        Self {
+            cells: cells.into_iter().map(Into::into).collect(),
+            height: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the height of the row in lines.
+    ///
+    /// By default, the row is as high as its tallest cell.
+    pub fn height(mut self, height: u16) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Sets the style of the row.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Helper to get the number of lines the row takes.
+    fn line_count(&self) -> u16 {
+        self.height.unwrap_or_else(|| {
+            let tallest = self.cells.iter().map(|c| c.content.height()).max();
+            tallest.unwrap_or(0).clamp(1, u16::MAX as usize) as u16
+        })
+    }
+}
+
+/// The selection and scroll offset of a [`Table`], kept between frames.
+///
+/// Like [`ListState`](crate::widgets::ListState), the navigation helpers
+/// don't know how many rows there are; rendering clamps the selection and
+/// scrolls so it stays visible.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TableState {
+    selection: Selection,
+}
+
+impl TableState {
+    /// Creates a state with no row selected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the selected row.
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.select(selected);
+        self
+    }
+
+    /// Returns the index of the selected row, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.selection.selected
+    }
+
+    /// Returns the index of the first visible row.
+    pub fn offset(&self) -> usize {
+        self.selection.offset
+    }
+
+    /// Selects a row, or clears the selection and scrolls to the top.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selection.select(index);
+    }
+
+    /// Selects the next row, or the first one if nothing is selected.
+    pub fn select_next(&mut self) {
+        self.selection.select_next();
+    }
+
+    /// Selects the previous row, or the last one if nothing is selected.
+    pub fn select_previous(&mut self) {
+        self.selection.select_previous();
+    }
+
+    /// Selects the first row.
+    pub fn select_first(&mut self) {
+        self.selection.select_first();
+    }
+
+    /// Selects the last row.
+    pub fn select_last(&mut self) {
+        self.selection.select_last();
+    }
+
+    /// Moves the selection down by the number of rows last shown.
+    pub fn page_down(&mut self) {
+        self.selection.page_down();
+    }
+
+    /// Moves the selection up by the number of rows last shown.
+    pub fn page_up(&mut self) {
+        self.selection.page_up();
+    }
+
+    /// Updates the state for a key press. Returns `true` if the key was used.
+    ///
+    /// The keys are the same as [`ListState::handle_key`](crate::widgets::ListState::handle_key),
+    /// without marking.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        self.selection.handle_key(key)
+    }
+
+    /// Helper to clamp the state to `rows` shown in `height` lines, scrolling
+    /// so that the selected row is fully visible when it fits.
+    fn clamp(&mut self, rows: &[Row], height: u16) {
+        let line_count = |i: usize| rows[i].line_count() as usize;
+        self.selection
+            .clamp(rows.len(), height as usize, line_count);
+    }
+}
+
+/// A widget that displays rows of cells aligned in columns.
+///
+/// Column widths are resolved from [`Constraint`]s like a [`Layout`]. Cell
+/// lines wider than their column are truncated with an ellipsis. The header
+/// is drawn with the theme's [`Role::Title`] style, the footer with
+/// [`Role::Muted`] and the selected row with [`Role::Selection`].
+///
+/// # Example
+/// ```
+/// use phosphor::{Buffer, Constraint, Frame, Rect, widgets::{Row, Table, TableState}};
+///
+/// let mut buffer = Buffer::new(12, 3);
+/// let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 12, 3));
+/// let table = Table::new(
+///     vec![Row::new(["init", "1"]), Row::new(["phosphor", "4242"])],
+///     vec![Constraint::Length(6), Constraint::Fill],
+/// )
+/// .header(Row::new(["NAME", "PID"]));
+///
+/// frame.render_stateful_widget(table, Rect::new(0, 0, 12, 3), &mut TableState::new());
+///
+/// let row: String = (0..12).map(|x| buffer.get(x, 2).symbol).collect();
+/// assert_eq!(row, "phosp… 4242 ");
+/// ```
+pub struct Table {
+    rows: Vec<Row>,
+    widths: Vec<Constraint>,
+    header: Option<Row>,
+    footer: Option<Row>,
+    column_spacing: u16,
+    style: Style,
+    highlight_style: Style,
+    highlight_symbol: String,
+}
+
+impl Table {
+    /// Creates a table with the given rows and column widths.
+    pub fn new(rows: Vec<Row>, widths: Vec<Constraint>) -> Self {
+        Self {
+            rows,
+            widths,
+            header: None,
+            footer: None,
+            column_spacing: 1,
+            style: Style::default(),
+            highlight_style: Style::default(),
+            highlight_symbol: String::new(),
+        }
+    }
+
+    /// Sets the row drawn above the rows. It doesn't scroll.
+    pub fn header(mut self, header: Row) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Sets the row drawn below the rows. It doesn't scroll.
+    pub fn footer(mut self, footer: Row) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// Sets the number of columns between two table columns. Defaults to 1.
+    pub fn column_spacing(mut self, spacing: u16) -> Self {
+        self.column_spacing = spacing;
+        self
+    }
+
+    /// Sets the base style of the table, patched over the theme's
+    /// [`Role::Text`] style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the selected row, patched over the theme's
+    /// [`Role::Selection`] style.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Sets the symbol drawn before the selected row.
+    ///
+    /// Every row is indented by the same width so the columns stay aligned.
+    pub fn highlight_symbol(mut self, symbol: &str) -> Self {
+        self.highlight_symbol = symbol.to_string();
+        self
+    }
+
+    /// Helper to draw a row at the top of `area`, with its cells in `columns`.
+    ///
+    /// The row and cell styles are patched over `style`, then `overlay` over
+    /// them, so the selection isn't hidden by a row's own colors.
+    fn render_row(
+        &self,
+        row: &Row,
+        area: Rect,
+        columns: &[Rect],
+        (style, overlay): (Style, Style),
+        frame: &mut Frame,
+    ) {
+        let style = style.patch(row.style);
+        frame.with_style(style, |f| {
+            for (cell, column) in row.cells.iter().zip(columns) {
+                let cell_area = Rect::new(column.x, area.y, column.width, area.height);
+                f.with_style(cell.style.patch(overlay), |f| {
+                    for (line, line_area) in cell.content.lines.iter().zip(cell_area.rows()) {
+                        f.render_line(&truncate(line, column.width as usize), line_area);
+                    }
+                });
+            }
+        });
+    }
+}
+
+impl StatefulWidget for Table {
+    type State = TableState;
+
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut TableState) {
+        let mut body = area;
+        let header_area = self
+            .header
+            .as_ref()
+            .map(|header| body.take_top(header.line_count()));
+        let footer_area = self
+            .footer
+            .as_ref()
+            .map(|footer| body.take_bottom(footer.line_count()));
+        state.clamp(&self.rows, body.height);
+        if area.is_empty() {
+            return;
+        }
+
+        let symbol = Line::raw(self.highlight_symbol.as_str());
+        let symbol_width = (symbol.width().min(area.width as usize)) as u16;
+        let (_, columns_area) = area.split_at_x(symbol_width);
+        let columns = Layout::new(Direction::Horizontal, self.widths.clone())
+            .spacing(self.column_spacing)
+            .split(columns_area);
+
+        let base = frame.role_style(Role::Text).patch(self.style);
+        let selected_style = frame
+            .role_style(Role::Selection)
+            .patch(self.highlight_style);
+
+        if let (Some(header), Some(header_area)) = (&self.header, header_area) {
+            let style = base.patch(frame.role_style(Role::Title));
+            self.render_row(header, header_area, &columns, (style, Style::new()), frame);
+        }
+        if let (Some(footer), Some(footer_area)) = (&self.footer, footer_area) {
+            let style = base.patch(frame.role_style(Role::Muted));
+            self.render_row(footer, footer_area, &columns, (style, Style::new()), frame);
+        }
+
+        let blank = Line::raw(" ".repeat(area.width as usize));
+        for (index, row) in self.rows.iter().enumerate().skip(state.offset()) {
+            if body.is_empty() {
+                break;
+            }
+            let row_area = body.take_top(row.line_count());
+            if state.selected() != Some(index) {
+                self.render_row(row, row_area, &columns, (base, Style::new()), frame);
+                continue;
+            }
+
+            let style = base.patch(row.style).patch(selected_style);
+            frame.with_style(style, |f| {
+                // Fill the row so the background covers the whole width.
+                for line_area in row_area.rows() {
+                    f.render_line(&blank, line_area);
+                }
+                f.render_line(&symbol, Rect::new(row_area.x, row_area.y, symbol_width, 1));
+            });
+            self.render_row(row, row_area, &columns, (base, selected_style), frame);
+        }
+    }
+}
+
+impl Widget for Table {
+    /// Draws the table without a selection, from the first row.
+    fn render(self, area: Rect, frame: &mut Frame) {
+        frame.render_stateful_widget(self, area, &mut TableState::default());
+    }
+}
+
+/// Helper to shorten a line to `width` columns, ending it with an ellipsis
+/// when content is cut.
+fn truncate(line: &Line, width: usize) -> Line {
+    if line.width() <= width {
+        return line.clone();
+    }
+
+    let mut truncated = Line::new(Vec::new());
+    truncated.alignment = line.alignment;
+    let Some(mut room) = width.checked_sub(1) else {
+        return truncated;
+    };
+    let mut style = Style::default();
+    for span in &line.spans {
+        style = span.style;
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let w = char_width(c);
+            if w > room {
+                room = 0;
+                break;
+            }
+            room -= w;
+            content.push(c);
+        }
+        truncated.spans.push(Span::styled(content, style));
+        if room == 0 {
+            break;
+        }
+    }
+    truncated.spans.push(Span::styled(ELLIPSIS, style));
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color, KeyCode, Theme};
+
+    #[test]
+    fn test_table_columns_header_and_footer() {
+        let mut buffer = Buffer::new(14, 4);
+        let area = Rect::new(0, 0, 14, 4);
+        let mut frame = Frame::new(&mut buffer, area);
+        frame.set_theme(Theme::new().with(Role::Title, Style::new().fg(Color::Yellow)));
+        let table = Table::new(
+            vec![Row::new(["a", "bb", "c"]), Row::new(["dd", "e", "ff"])],
+            vec![
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Fill,
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(["A", "B", "C"]))
+        .footer(Row::new(["", "", "total"]));
+
+        frame.render_widget(table, area);
+
+        assert_eq!(buffer.row_symbols(0), "A    B    C   ");
+        assert_eq!(buffer.row_symbols(1), "a    bb   c   ");
+        assert_eq!(buffer.row_symbols(2), "dd   e    ff  ");
+        assert_eq!(buffer.row_symbols(3), "          tot…");
+        assert_eq!(buffer.get(0, 0).style.foreground, Some(Color::Yellow));
+        assert_eq!(buffer.get(0, 1).style.foreground, None);
+    }
+
+    #[test]
+    fn test_table_selection_and_multiline_rows() {
+        let mut buffer = Buffer::new(8, 3);
+        let area = Rect::new(0, 0, 8, 3);
+        let mut state = TableState::new().with_selected(Some(2));
+        let rows = vec![
+            Row::new(["r0"]),
+            Row::new(["r1\nmore"]),
+            Row::new(["r2"]).style(Style::new().fg(Color::Red)),
+        ];
+        let table = Table::new(rows, vec![Constraint::Fill])
+            .highlight_symbol(">")
+            .highlight_style(Style::new().bg(Color::Blue));
+
+        Frame::new(&mut buffer, area).render_stateful_widget(table, area, &mut state);
+
+        assert_eq!(state.offset(), 1);
+        assert_eq!(buffer.row_symbols(0), " r1     ");
+        assert_eq!(buffer.row_symbols(1), " more   ");
+        assert_eq!(buffer.row_symbols(2), ">r2     ");
+        assert_eq!(buffer.get(7, 2).style.background, Some(Color::Blue));
+        assert_eq!(buffer.get(1, 2).style.foreground, Some(Color::Red));
+        assert_eq!(buffer.get(7, 1).style.background, None);
+    }
+
+    #[test]
+    fn test_table_selection_over_row_background() {
+        let mut buffer = Buffer::new(4, 1);
+        let area = Rect::new(0, 0, 4, 1);
+        let mut state = TableState::new().with_selected(Some(0));
+        let rows = vec![Row::new(["ab"]).style(Style::new().fg(Color::Red).bg(Color::Black))];
+        let table =
+            Table::new(rows, vec![Constraint::Fill]).highlight_style(Style::new().bg(Color::Blue));
+
+        Frame::new(&mut buffer, area).render_stateful_widget(table, area, &mut state);
+
+        for x in 0..4 {
+            assert_eq!(buffer.get(x, 0).style.background, Some(Color::Blue));
+        }
+        assert_eq!(buffer.get(0, 0).style.foreground, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_table_state_keys_and_clamping() {
+        let rows = || {
+            (0..10)
+                .map(|i| Row::new([i.to_string()]))
+                .collect::<Vec<_>>()
+        };
+        let mut buffer = Buffer::new(4, 4);
+        let area = Rect::new(0, 0, 4, 4);
+        let mut state = TableState::new();
+        let mut render = |state: &mut TableState| {
+            let table = Table::new(rows(), vec![Constraint::Fill]).header(Row::new(["#"]));
+            Frame::new(&mut buffer, area).render_stateful_widget(table, area, state);
+        };
+
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::End)));
+        render(&mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(9), 7));
+
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::PageUp)));
+        render(&mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(6), 6));
+
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::Char('g'))));
+        assert!(!state.handle_key(&KeyEvent::new(KeyCode::Char(' '))));
+        render(&mut state);
+        assert_eq!((state.selected(), state.offset()), (Some(0), 0));
+    }
+
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        let line = Line::new(vec![Span::raw("ab"), Span::styled("cdef", Style::new())]);
+        let text = |l: Line| {
+            l.spans
+                .iter()
+                .map(|s| s.content.clone())
+                .collect::<String>()
+        };
+
+        assert_eq!(text(truncate(&line, 6)), "abcdef");
+        assert_eq!(text(truncate(&line, 4)), "abc…");
+        assert_eq!(text(truncate(&line, 1)), "…");
+        assert_eq!(text(truncate(&line, 0)), "");
+        assert_eq!(text(truncate(&Line::raw("日本語"), 4)), "日…");
+    }
+}
@@ -1,25 +1,36 @@
-//! A simple widget that displays a string of text.
+//! A widget that displays styled, optionally wrapped, multi-line text.
 
-use crate::{Frame, Rect, Style, widgets::Widget};
+use crate::{
+    Alignment, Frame, Line, Rect, Role, Size, Span, Style, StyledText,
+    widgets::{Measure, Widget, WidgetRef},
+};
 
-/// A simple widget that displays a string of text.
+/// A widget that displays styled, optionally wrapped, multi-line text.
+///
+/// Accepts anything convertible to [`StyledText`]: plain strings (split on
+/// newlines), [`Span`]s, [`Line`]s or whole [`StyledText`] values.
 pub struct Text {
-    text: String,
+    text: StyledText,
     style: Style,
     wrap: bool,
+    alignment: Alignment,
 }
 
 impl Text {
     /// Creates a new text widget.
-    pub fn new<S: Into<String>>(text: S) -> Self {
+    pub fn new<T: Into<StyledText>>(text: T) -> Self {
         Self {
             text: text.into(),
             style: Style::default(),
             wrap: false,
+            alignment: Alignment::Left,
         }
     }
 
-    /// Sets the style of the text.
+    /// Sets the base style of the text.
+    ///
+    /// It is patched over the theme's [`Role::Text`] style, and spans are
+    /// patched over it.
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
@@ -32,49 +43,142 @@ impl Text {
         self.wrap = wrapped;
         self
     }
+
+    /// Sets the alignment used for lines that don't specify their own.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
 }
 
 impl Widget for Text {
     fn render(self, area: Rect, frame: &mut Frame) {
-        frame.render_area(area, |f| {
-            f.with_style(self.style, |f| {
-                if self.wrap {
-                    let mut wx: u16 = 0;
-                    let mut wy: u16 = 0;
-
-                    for line in self.text.lines() {
-                        for w in line.split_whitespace() {
-                            if wx + w.len() as u16 > f.width() {
-                                wx = 0;
-                                wy += 1;
-                            }
-                            if wy >= f.height() {
-                                break;
-                            }
-
-                            f.write_str(wx, wy, w);
-                            wx += w.len() as u16 + 1;
-                        }
-
-                        // End of paragraph: force new line
-                        wx = 0;
-                        wy += 1;
-                        if wy >= f.height() {
-                            break;
-                        }
-                    }
-                } else {
-                    f.write_str(0, 0, &self.text);
-                }
-            });
+        self.render_ref(area, frame);
+    }
+}
+
+impl WidgetRef for Text {
+    fn render_ref(&self, area: Rect, frame: &mut Frame) {
+        let wrapped: Vec<Line>;
+        let lines = if self.wrap {
+            wrapped = self
+                .text
+                .lines
+                .iter()
+                .flat_map(|line| wrap_line(line, area.width as usize))
+                .collect();
+            &wrapped
+        } else {
+            &self.text.lines
+        };
+
+        let style = frame.role_style(Role::Text).patch(self.style);
+        frame.with_style(style, |f| {
+            for (line, row) in lines.iter().zip(area.rows()) {
+                f.render_line_aligned(line, row, self.alignment);
+            }
         });
     }
 }
 
+impl Measure for Text {
+    /// Returns the width of the widest line and the number of lines, after
+    /// wrapping to `max_width` if wrapping is enabled.
+    fn preferred_size(&self, max_width: u16) -> Size {
+        self.measure(max_width as usize, max_width)
+    }
+
+    /// For wrapped text, returns the size when wrapped as narrowly as
+    /// possible: the width of the widest word.
+    fn min_size(&self, max_width: u16) -> Size {
+        if !self.wrap {
+            return self.preferred_size(max_width);
+        }
+        // Wrapping at zero puts every word on a line of its own.
+        let widest_word = self.measure(0, max_width).width;
+        self.measure(widest_word as usize, max_width)
+    }
+}
+
+impl Text {
+    /// Helper to measure the text wrapped at `wrap_width`, clipped to `max_width`.
+    fn measure(&self, wrap_width: usize, max_width: u16) -> Size {
+        let (width, height) = if self.wrap {
+            self.text
+                .lines
+                .iter()
+                .flat_map(|line| wrap_line(line, wrap_width))
+                .fold((0, 0), |(w, h), line| (line.width().max(w), h + 1))
+        } else {
+            (self.text.width(), self.text.height())
+        };
+        Size::new(
+            width.min(max_width as usize) as u16,
+            height.min(u16::MAX as usize) as u16,
+        )
+    }
+}
+
+/// Word-wraps a line so that every resulting line fits in `width` columns.
+///
+/// Words are split on whitespace and re-joined with a single space. Words
+/// wider than `width` get a line of their own and are clipped when drawn.
+fn wrap_line(line: &Line, width: usize) -> Vec<Line> {
+    // 1. Split the spans into words, keeping per-span styles.
+    let mut words: Vec<Vec<Span>> = Vec::new();
+    let mut word: Vec<Span> = Vec::new();
+    for span in &line.spans {
+        let mut piece = String::new();
+        for c in span.content.chars() {
+            if c.is_whitespace() {
+                if !piece.is_empty() {
+                    word.push(Span::styled(std::mem::take(&mut piece), span.style));
+                }
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            } else {
+                piece.push(c);
+            }
+        }
+        if !piece.is_empty() {
+            word.push(Span::styled(piece, span.style));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    // 2. Lay the words out greedily.
+    let new_line = || Line {
+        spans: Vec::new(),
+        alignment: line.alignment,
+    };
+    let mut lines = Vec::new();
+    let mut current = new_line();
+    let mut x = 0;
+    for word in words {
+        let w: usize = word.iter().map(Span::width).sum();
+        if x > 0 && x + 1 + w > width {
+            lines.push(std::mem::replace(&mut current, new_line()));
+            x = 0;
+        }
+        if x > 0 {
+            current.spans.push(Span::raw(" "));
+            x += 1;
+        }
+        current.spans.extend(word);
+        x += w;
+    }
+    lines.push(current);
+
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Buffer, Color};
+    use crate::{Buffer, Color, Modifier};
 
     #[test]
     fn test_text_render() {
@@ -117,4 +221,51 @@ mod tests {
         assert_eq!(buffer.get(0, 1).symbol, 'W');
         assert_eq!(buffer.get(4, 1).symbol, 'd');
     }
+
+    #[test]
+    fn test_text_measure() {
+        let text = Text::new("Hello wide World\nHi");
+        assert_eq!(text.preferred_size(80), Size::new(16, 2));
+        assert_eq!(text.preferred_size(10), Size::new(10, 2));
+        assert_eq!(text.min_size(10), Size::new(10, 2));
+
+        let wrapped = Text::new("Hello wide World\nHi").wrap(true);
+        assert_eq!(wrapped.preferred_size(80), Size::new(16, 2));
+        assert_eq!(wrapped.preferred_size(11), Size::new(10, 3));
+        assert_eq!(wrapped.min_size(80), Size::new(5, 4));
+        assert_eq!(wrapped.min_size(3), Size::new(3, 4));
+    }
+
+    #[test]
+    fn test_text_multiline_without_wrap() {
+        let mut buffer = Buffer::new(5, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 2));
+
+        Text::new("ab\ncd").render(Rect::new(0, 0, 5, 2), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        assert_eq!(buffer.get(0, 1).symbol, 'c');
+        assert_eq!(buffer.get(2, 0).symbol, ' ');
+    }
+
+    #[test]
+    fn test_text_rich_spans_wrap_and_align() {
+        let mut buffer = Buffer::new(6, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 2));
+        let bold = Style::new().modifier(Modifier::BOLD);
+        let line = Line::new(vec![Span::styled("Err", bold), Span::raw("or: x")]);
+
+        Text::new(line)
+            .wrap(true)
+            .alignment(Alignment::Right)
+            .render(Rect::new(0, 0, 6, 2), &mut frame);
+
+        // "Error:" fills the first row, with a styled prefix.
+        assert_eq!(buffer.get(0, 0).symbol, 'E');
+        assert_eq!(buffer.get(0, 0).style, bold);
+        assert_eq!(buffer.get(3, 0).symbol, 'o');
+        assert_eq!(buffer.get(3, 0).style, Style::default());
+        // "x" is right-aligned on the second row.
+        assert_eq!(buffer.get(5, 1).symbol, 'x');
+    }
 }
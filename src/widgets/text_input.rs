@@ -0,0 +1,709 @@
+//! A single-line text field with readline-style editing.
+
+use std::ops::Range;
+
+use crate::{
+    Frame, KeyCode, KeyEvent, Rect, Role, Style,
+    input::KeyModifiers,
+    text::char_width,
+    widgets::{Block, StatefulWidget, Widget, WidgetRef},
+};
+
+/// The content, caret and selection of a [`TextInput`], kept between frames.
+///
+/// Positions are counted in characters, not bytes.
+///
+/// # Example
+/// ```
+/// use phosphor::{KeyCode, KeyEvent, widgets::InputState};
+///
+/// let mut state = InputState::new().with_value("hello world");
+/// state.handle_key(&KeyEvent::new(KeyCode::Char('!')));
+/// assert_eq!(state.value(), "hello world!");
+///
+/// let ctrl_w = KeyEvent::with_modifiers(KeyCode::Char('w'), phosphor::input::KeyModifiers::CTRL);
+/// state.handle_key(&ctrl_w);
+/// assert_eq!(state.value(), "hello ");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InputState {
+    value: String,
+    cursor: usize,
+    /// The other end of the selection, if any; the caret is the first end.
+    anchor: Option<usize>,
+    /// The index of the first visible character.
+    offset: usize,
+    history: Vec<String>,
+    /// The history entry being shown, if any.
+    history_index: Option<usize>,
+    /// The value being edited before browsing the history.
+    draft: String,
+    /// Where the last render drew the caret.
+    cursor_position: Option<(u16, u16)>,
+}
+
+impl InputState {
+    /// Creates an empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value, with the caret at the end.
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the entries recalled with `Up` and `Down`, oldest first.
+    pub fn with_history(mut self, history: Vec<String>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the value, moving the caret to the end and clearing the
+    /// selection.
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        self.value = value.into();
+        self.cursor = self.len();
+        self.anchor = None;
+    }
+
+    /// Clears the value.
+    pub fn clear(&mut self) {
+        self.set_value("");
+    }
+
+    /// Returns the caret position, in characters.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the selected character range, if it isn't empty.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// Returns the selected text, or an empty string.
+    pub fn selected_text(&self) -> &str {
+        self.selection()
+            .map_or("", |range| &self.value[self.byte_range(range)])
+    }
+
+    /// Selects the whole value.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.len();
+    }
+
+    /// Returns the entries recalled with `Up` and `Down`, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Returns where the last render drew the caret, in absolute
+    /// coordinates, or `None` if it wasn't visible.
+    pub fn cursor_position(&self) -> Option<(u16, u16)> {
+        self.cursor_position
+    }
+
+    /// Takes the value, adds it to the history and clears the input.
+    ///
+    /// Empty values and repeats of the last entry are not added.
+    pub fn submit(&mut self) -> String {
+        let value = std::mem::take(&mut self.value);
+        if !value.is_empty() && self.history.last() != Some(&value) {
+            self.history.push(value.clone());
+        }
+        self.history_index = None;
+        self.draft.clear();
+        self.clear();
+        value
+    }
+
+    /// Inserts a character at the caret, replacing the selection.
+    pub fn insert_char(&mut self, c: char) {
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Inserts text at the caret, replacing the selection. Control
+    /// characters, including newlines, are skipped.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        let at = self.byte_index(self.cursor);
+        self.value.insert_str(at, &text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Deletes the selection, or the character before the caret.
+    pub fn delete_backward(&mut self) {
+        if !self.delete_selection() {
+            self.delete(self.cursor.saturating_sub(1)..self.cursor);
+        }
+    }
+
+    /// Deletes the selection, or the character after the caret.
+    pub fn delete_forward(&mut self) {
+        if !self.delete_selection() {
+            self.delete(self.cursor..(self.cursor + 1).min(self.len()));
+        }
+    }
+
+    /// Deletes the selection, or from the start of the word before the caret.
+    pub fn delete_word_backward(&mut self) {
+        if !self.delete_selection() {
+            self.delete(self.word_start()..self.cursor);
+        }
+    }
+
+    /// Deletes the selection, or to the end of the word after the caret.
+    pub fn delete_word_forward(&mut self) {
+        if !self.delete_selection() {
+            self.delete(self.cursor..self.word_end());
+        }
+    }
+
+    /// Deletes everything before the caret.
+    pub fn delete_to_start(&mut self) {
+        self.anchor = None;
+        self.delete(0..self.cursor);
+    }
+
+    /// Deletes everything after the caret.
+    pub fn delete_to_end(&mut self) {
+        self.anchor = None;
+        self.delete(self.cursor..self.len());
+    }
+
+    /// Moves the caret one character left. If `select` is true, the
+    /// selection is extended instead of cleared.
+    pub fn move_left(&mut self, select: bool) {
+        self.move_to(self.cursor.saturating_sub(1), select);
+    }
+
+    /// Moves the caret one character right. See [`move_left`](Self::move_left).
+    pub fn move_right(&mut self, select: bool) {
+        self.move_to((self.cursor + 1).min(self.len()), select);
+    }
+
+    /// Moves the caret to the start of the previous word.
+    pub fn move_word_left(&mut self, select: bool) {
+        self.move_to(self.word_start(), select);
+    }
+
+    /// Moves the caret to the end of the next word.
+    pub fn move_word_right(&mut self, select: bool) {
+        self.move_to(self.word_end(), select);
+    }
+
+    /// Moves the caret to the start of the value.
+    pub fn move_home(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    /// Moves the caret to the end of the value.
+    pub fn move_end(&mut self, select: bool) {
+        self.move_to(self.len(), select);
+    }
+
+    /// Replaces the value with the previous history entry. The value being
+    /// edited is kept and comes back after the newest entry.
+    pub fn history_previous(&mut self) {
+        let index = match self.history_index {
+            _ if self.history.is_empty() => return,
+            None => {
+                self.draft = self.value.clone();
+                self.history.len() - 1
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+        self.history_index = Some(index);
+        self.set_value(self.history[index].clone());
+    }
+
+    /// Replaces the value with the next history entry, or the value that
+    /// was being edited after the newest one.
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.set_value(self.history[index + 1].clone());
+        } else {
+            self.history_index = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.set_value(draft);
+        }
+    }
+
+    /// Updates the state for a key press. Returns `true` if the key was used.
+    ///
+    /// | Keys                          | Action                            |
+    /// |-------------------------------|-----------------------------------|
+    /// | characters                    | insert                            |
+    /// | `Left`/`Right`, `Ctrl-B`/`F`  | move by a character               |
+    /// | `Ctrl`/`Alt` + arrows, `Alt-B`/`F` | move by a word               |
+    /// | `Home`/`End`, `Ctrl-A`/`E`    | move to the start or end          |
+    /// | `Shift` + any move key        | extend the selection              |
+    /// | `Backspace`, `Delete`/`Ctrl-D`| delete a character                |
+    /// | `Ctrl-W`/`Alt-Backspace`, `Alt-D` | delete a word                 |
+    /// | `Ctrl-U`, `Ctrl-K`            | delete to the start or end        |
+    /// | `Up`/`Ctrl-P`, `Down`/`Ctrl-N`| recall the history                |
+    ///
+    /// `Enter` is left to the application, which usually calls
+    /// [`submit`](Self::submit).
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CTRL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let word = ctrl || alt;
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.move_home(false),
+            KeyCode::Char('e') if ctrl => self.move_end(false),
+            KeyCode::Char('b') if ctrl => self.move_left(false),
+            KeyCode::Char('f') if ctrl => self.move_right(false),
+            KeyCode::Char('b') if alt => self.move_word_left(false),
+            KeyCode::Char('f') if alt => self.move_word_right(false),
+            KeyCode::Char('d') if ctrl => self.delete_forward(),
+            KeyCode::Char('d') if alt => self.delete_word_forward(),
+            KeyCode::Char('w') if ctrl => self.delete_word_backward(),
+            KeyCode::Char('u') if ctrl => self.delete_to_start(),
+            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Char('p') if ctrl => self.history_previous(),
+            KeyCode::Char('n') if ctrl => self.history_next(),
+            KeyCode::Char(_) if word => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Left if word => self.move_word_left(shift),
+            KeyCode::Right if word => self.move_word_right(shift),
+            KeyCode::Left => self.move_left(shift),
+            KeyCode::Right => self.move_right(shift),
+            KeyCode::Home => self.move_home(shift),
+            KeyCode::End => self.move_end(shift),
+            KeyCode::Backspace if word => self.delete_word_backward(),
+            KeyCode::Backspace => self.delete_backward(),
+            KeyCode::Delete => self.delete_forward(),
+            KeyCode::Up => self.history_previous(),
+            KeyCode::Down => self.history_next(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Helper to get the number of characters in the value.
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    /// Helper to convert a character index to a byte index.
+    fn byte_index(&self, index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    /// Helper to convert a character range to a byte range.
+    fn byte_range(&self, range: Range<usize>) -> Range<usize> {
+        self.byte_index(range.start)..self.byte_index(range.end)
+    }
+
+    /// Helper to move the caret, extending or clearing the selection.
+    fn move_to(&mut self, index: usize, select: bool) {
+        if !select {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.cursor);
+        }
+        self.cursor = index;
+    }
+
+    /// Helper to delete a character range and put the caret at its start.
+    fn delete(&mut self, range: Range<usize>) {
+        let start = range.start;
+        let bytes = self.byte_range(range);
+        self.value.replace_range(bytes, "");
+        self.cursor = start;
+    }
+
+    /// Helper to delete the selection. Returns `false` if there was none.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        match selection {
+            Some(range) => {
+                self.delete(range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Helper to find the start of the word before the caret, skipping the
+    /// separators in between.
+    fn word_start(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().take(self.cursor).collect();
+        let separators = chars.iter().rev().take_while(|c| !is_word(**c)).count();
+        let word = chars
+            .iter()
+            .rev()
+            .skip(separators)
+            .take_while(|c| is_word(**c))
+            .count();
+        self.cursor - separators - word
+    }
+
+    /// Helper to find the end of the word after the caret, skipping the
+    /// separators in between.
+    fn word_end(&self) -> usize {
+        let mut chars = self.value.chars().skip(self.cursor).peekable();
+        let mut end = self.cursor;
+        while chars.next_if(|c| !is_word(*c)).is_some() {
+            end += 1;
+        }
+        while chars.next_if(|c| is_word(*c)).is_some() {
+            end += 1;
+        }
+        end
+    }
+
+    /// Helper to scroll so the caret fits in `width` columns, and the end of
+    /// the value doesn't leave space on the right when it can be avoided.
+    fn scroll(&mut self, width: usize, mask: Option<char>) {
+        let widths: Vec<usize> = self
+            .value
+            .chars()
+            .map(|c| char_width(mask.unwrap_or(c)))
+            .collect();
+        let span = |range: Range<usize>| -> usize { widths[range].iter().sum() };
+
+        self.offset = self.offset.min(self.cursor);
+        // Keep a column for the caret after the last character.
+        while self.offset < self.cursor && span(self.offset..self.cursor) + 1 > width {
+            self.offset += 1;
+        }
+        while self.offset > 0 && span(self.offset - 1..widths.len()) < width {
+            self.offset -= 1;
+        }
+    }
+}
+
+/// Returns `true` for characters that are part of a word.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// A single-line text field, editing an [`InputState`].
+///
+/// The value scrolls horizontally to keep the caret visible. The selection
+/// is drawn with the theme's [`Role::Selection`] style and the placeholder
+/// with [`Role::Muted`]. The terminal cursor is placed at the caret (see
+/// [`Frame::set_cursor_position`]), and its position is also stored in the
+/// state, see [`InputState::cursor_position`].
+///
+/// # Example
+/// ```
+/// use phosphor::{Buffer, Frame, Rect, widgets::{InputState, TextInput}};
+///
+/// let mut buffer = Buffer::new(8, 1);
+/// let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 8, 1));
+/// let mut state = InputState::new().with_value("hunter2");
+///
+/// frame.render_stateful_widget(TextInput::new().mask('*'), Rect::new(0, 0, 8, 1), &mut state);
+///
+/// let row: String = (0..8).map(|x| buffer.get(x, 0).symbol).collect();
+/// assert_eq!(row, "******* ");
+/// assert_eq!(state.cursor_position(), Some((7, 0)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    placeholder: String,
+    mask: Option<char>,
+    style: Style,
+    selection_style: Style,
+    block: Option<Block>,
+    /// Whether to leave the terminal cursor alone.
+    hide_cursor: bool,
+}
+
+impl TextInput {
+    /// Creates an empty text field.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text shown while the value is empty.
+    pub fn placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Draws every character of the value as `mask`, e.g. for passwords.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Sets the style of the value, patched over the theme's [`Role::Text`]
+    /// style.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the selection, patched over the theme's
+    /// [`Role::Selection`] style.
+    pub fn selection_style(mut self, style: Style) -> Self {
+        self.selection_style = style;
+        self
+    }
+
+    /// Wraps the field in a block.
+    pub fn block(mut self, block: Block) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets whether the terminal cursor is placed at the caret. Defaults to
+    /// `true`; turn it off for fields without focus.
+    pub fn show_cursor(mut self, show: bool) -> Self {
+        self.hide_cursor = !show;
+        self
+    }
+}
+
+impl StatefulWidget for TextInput {
+    type State = InputState;
+
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut InputState) {
+        let area = match &self.block {
+            Some(block) => {
+                block.render_ref(area, frame);
+                block.inner(area)
+            }
+            None => area,
+        };
+        state.cursor_position = None;
+        if area.is_empty() {
+            return;
+        }
+
+        let width = area.width as usize;
+        state.scroll(width, self.mask);
+        let base = frame.role_style(Role::Text).patch(self.style);
+        let blank = " ".repeat(width);
+        frame.render_area(area, |f| f.write_str_with_style(0, 0, &blank, base));
+
+        if state.value.is_empty() {
+            let placeholder = frame.role_style(Role::Muted);
+            frame.with_style(base.patch(placeholder), |f| {
+                f.render_line(&self.placeholder.as_str().into(), area);
+            });
+        }
+
+        let selected = frame
+            .role_style(Role::Selection)
+            .patch(self.selection_style);
+        let selection = state.selection().unwrap_or_default();
+        let mut x = 0;
+        let mut caret = (state.cursor == state.offset).then_some(0);
+        let mut buf = [0u8; 4];
+        frame.render_area(area, |f| {
+            for (i, c) in state.value.chars().enumerate().skip(state.offset) {
+                let c = self.mask.unwrap_or(c);
+                let w = char_width(c);
+                if x + w > width {
+                    break;
+                }
+                let style = if selection.contains(&i) {
+                    selected
+                } else {
+                    base
+                };
+                f.write_str_with_style(x as u16, 0, c.encode_utf8(&mut buf), style);
+                x += w;
+                if i + 1 == state.cursor {
+                    caret = Some(x);
+                }
+            }
+        });
+
+        state.cursor_position = caret
+            .filter(|&x| x < width)
+            .map(|x| (area.x + x as u16, area.y));
+        if let Some((x, y)) = state.cursor_position
+            && !self.hide_cursor
+        {
+            frame.set_cursor_position(x, y);
+        }
+    }
+}
+
+impl Widget for TextInput {
+    /// Draws the field with an empty value, showing the placeholder.
+    fn render(self, area: Rect, frame: &mut Frame) {
+        frame.render_stateful_widget(self, area, &mut InputState::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color, Theme};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code)
+    }
+
+    fn with(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::with_modifiers(code, modifiers)
+    }
+
+    fn type_str(state: &mut InputState, text: &str) {
+        for c in text.chars() {
+            assert!(state.handle_key(&key(KeyCode::Char(c))));
+        }
+    }
+
+    #[test]
+    fn test_input_editing_keys() {
+        let mut state = InputState::new();
+        type_str(&mut state, "héllo wörld");
+        assert_eq!((state.value(), state.cursor()), ("héllo wörld", 11));
+
+        state.handle_key(&with(KeyCode::Char('b'), KeyModifiers::ALT));
+        assert_eq!(state.cursor(), 6);
+        state.handle_key(&with(KeyCode::Left, KeyModifiers::CTRL));
+        assert_eq!(state.cursor(), 0);
+        state.handle_key(&with(KeyCode::Char('f'), KeyModifiers::ALT));
+        assert_eq!(state.cursor(), 5);
+        state.handle_key(&key(KeyCode::Delete));
+        assert_eq!(state.value(), "héllowörld");
+
+        state.handle_key(&with(KeyCode::Char('k'), KeyModifiers::CTRL));
+        assert_eq!(state.value(), "héllo");
+        type_str(&mut state, " big, world");
+        state.handle_key(&with(KeyCode::Char('w'), KeyModifiers::CTRL));
+        assert_eq!(state.value(), "héllo big, ");
+        state.handle_key(&with(KeyCode::Char('w'), KeyModifiers::CTRL));
+        assert_eq!(state.value(), "héllo ");
+        state.handle_key(&key(KeyCode::Backspace));
+        state.handle_key(&with(KeyCode::Char('a'), KeyModifiers::CTRL));
+        state.handle_key(&key(KeyCode::Char('>')));
+        assert_eq!((state.value(), state.cursor()), (">héllo", 1));
+        state.handle_key(&key(KeyCode::End));
+        state.handle_key(&with(KeyCode::Char('u'), KeyModifiers::CTRL));
+        assert_eq!(state.value(), "");
+        assert!(!state.handle_key(&with(KeyCode::Char('z'), KeyModifiers::CTRL)));
+    }
+
+    #[test]
+    fn test_input_selection() {
+        let mut state = InputState::new().with_value("one two");
+        state.handle_key(&with(KeyCode::Left, KeyModifiers::SHIFT));
+        state.handle_key(&with(
+            KeyCode::Left,
+            KeyModifiers::SHIFT | KeyModifiers::CTRL,
+        ));
+        assert_eq!(state.selection(), Some(4..7));
+        assert_eq!(state.selected_text(), "two");
+
+        type_str(&mut state, "2");
+        assert_eq!(state.value(), "one 2");
+        assert_eq!(state.selection(), None);
+
+        state.select_all();
+        state.handle_key(&key(KeyCode::Backspace));
+        assert_eq!(state.value(), "");
+
+        state.set_value("abc");
+        state.handle_key(&with(KeyCode::Home, KeyModifiers::SHIFT));
+        state.handle_key(&key(KeyCode::Right));
+        assert_eq!((state.selection(), state.cursor()), (None, 1));
+    }
+
+    #[test]
+    fn test_input_history() {
+        let mut state = InputState::new().with_history(vec!["ls".into()]);
+        type_str(&mut state, "make");
+        assert_eq!(state.submit(), "make");
+        assert_eq!(state.submit(), "");
+        assert_eq!(state.history(), ["ls", "make"]);
+
+        type_str(&mut state, "dra");
+        state.handle_key(&key(KeyCode::Up));
+        assert_eq!(state.value(), "make");
+        state.handle_key(&with(KeyCode::Char('p'), KeyModifiers::CTRL));
+        state.handle_key(&key(KeyCode::Up));
+        assert_eq!(state.value(), "ls");
+        state.handle_key(&key(KeyCode::Down));
+        assert_eq!(state.value(), "make");
+        state.handle_key(&key(KeyCode::Down));
+        assert_eq!((state.value(), state.cursor()), ("dra", 3));
+    }
+
+    #[test]
+    fn test_input_render_scrolls_and_places_cursor() {
+        let mut buffer = Buffer::new(6, 1);
+        let area = Rect::new(0, 0, 6, 1);
+        let mut state = InputState::new().with_value("abcdefghij");
+        let mut render = |input: TextInput, state: &mut InputState| {
+            Frame::new(&mut buffer, area).render_stateful_widget(input, area, state);
+        };
+
+        render(TextInput::new(), &mut state);
+        assert_eq!(state.cursor_position(), Some((5, 0)));
+
+        state.move_home(false);
+        render(TextInput::new(), &mut state);
+        assert_eq!(state.cursor_position(), Some((0, 0)));
+
+        state.move_end(false);
+        state.delete_to_start();
+        render(TextInput::new(), &mut state);
+        assert_eq!(state.cursor_position(), Some((0, 0)));
+        let row: String = (0..6).map(|x| buffer.get(x, 0).symbol).collect();
+        assert_eq!(row, "      ");
+    }
+
+    #[test]
+    fn test_input_render_placeholder_and_selection() {
+        let mut buffer = Buffer::new(8, 2);
+        let area = Rect::new(0, 0, 8, 2);
+        let mut frame = Frame::new(&mut buffer, area);
+        frame.set_theme(
+            Theme::new()
+                .with(Role::Muted, Style::new().fg(Color::BrightBlack))
+                .with(Role::Selection, Style::new().bg(Color::Blue)),
+        );
+
+        let mut empty = InputState::new();
+        let input = TextInput::new().placeholder("Search");
+        frame.render_stateful_widget(input, Rect::new(0, 0, 8, 1), &mut empty);
+
+        let mut state = InputState::new().with_value("abc");
+        state.move_left(true);
+        frame.render_stateful_widget(TextInput::new(), Rect::new(0, 1, 8, 1), &mut state);
+        let cursor = frame.cursor().unwrap();
+        assert_eq!((cursor.x, cursor.y), (2, 1));
+
+        let unfocused = TextInput::new().placeholder("Search").show_cursor(false);
+        frame.render_stateful_widget(unfocused, Rect::new(0, 0, 8, 1), &mut empty);
+        assert_eq!(frame.cursor(), Some(cursor));
+
+        assert_eq!(buffer.get(0, 0).symbol, 'S');
+        assert_eq!(buffer.get(0, 0).style.foreground, Some(Color::BrightBlack));
+        assert_eq!(empty.cursor_position(), Some((0, 0)));
+        assert_eq!(buffer.get(2, 1).style.background, Some(Color::Blue));
+        assert_eq!(buffer.get(1, 1).style.background, None);
+        assert_eq!(state.cursor_position(), Some((2, 1)));
+    }
+}